@@ -56,6 +56,24 @@ public class BollardCodegen extends RustServerCodegen {
         enumToString.add("HostConfigLogConfig");
     }
 
+    // Integer properties the Docker API documents as nanoseconds, which are
+    // exposed as a `Duration` instead
+    private static ArrayList<String> durationProperties;
+    static {
+        durationProperties = new ArrayList();
+        durationProperties.add("HealthConfig.Interval");
+        durationProperties.add("HealthConfig.Timeout");
+        durationProperties.add("HealthConfig.StartPeriod");
+        durationProperties.add("ServiceSpecRollbackConfig.Delay");
+        durationProperties.add("ServiceSpecRollbackConfig.Monitor");
+        durationProperties.add("ServiceSpecUpdateConfig.Delay");
+        durationProperties.add("ServiceSpecUpdateConfig.Monitor");
+        durationProperties.add("SwarmSpecCaConfig.NodeCertExpiry");
+        durationProperties.add("SwarmSpecDispatcher.HeartbeatPeriod");
+        durationProperties.add("TaskSpecRestartPolicy.Delay");
+        durationProperties.add("TaskSpecRestartPolicy.Window");
+    }
+
     @Override
     public void preprocessSwagger(Swagger swagger) {
         Info info = swagger.getInfo();
@@ -129,6 +147,11 @@ public class BollardCodegen extends RustServerCodegen {
                     prop.vendorExtensions.put("x-rustgen-is-datetime", true);
                     prop.datatype = "BollardDate";
                 }
+                if (durationProperties.contains(model.classname + "." + prop.baseName)) {
+                    // set Duration format on nanosecond properties
+                    prop.vendorExtensions.put("x-rustgen-is-duration", true);
+                    prop.datatype = "Duration";
+                }
                 if (prop.isEnum) {
                     if (enumToString.contains(model.classname)) {
                         prop.isEnum = false;
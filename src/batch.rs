@@ -0,0 +1,50 @@
+use futures_util::stream::{self, StreamExt};
+
+use std::future::Future;
+
+/// Run a future for every item, with at most `max_concurrency` of them in flight at a time so
+/// batch operations don't flood the daemon with requests. Results are collected in the order the
+/// futures complete.
+pub(crate) async fn fan_out<I, F, Fut>(items: I, max_concurrency: usize, f: F) -> Vec<Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    stream::iter(items)
+        .map(f)
+        .buffer_unordered(max_concurrency.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fan_out;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_fan_out_max_concurrency() {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let mut results = fan_out(0..10, 3, |i| {
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i * 2
+            }
+        })
+        .await;
+        results.sort_unstable();
+
+        assert_eq!(results, (0..10).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+
+        assert_eq!(fan_out(0..2, 0, |i| async move { i }).await.len(), 2);
+    }
+}
@@ -1,21 +1,25 @@
 //! Container API: run docker containers and manage their lifecycle
 
 use futures_core::Stream;
-use futures_util::StreamExt;
+use futures_util::stream::{self, StreamExt};
 use http::header::{CONNECTION, CONTENT_TYPE, UPGRADE};
 use http::request::Builder;
-use hyper::{body::Bytes, Body, Method};
+use hyper::{body::Bytes, Body, Method, StatusCode};
 use serde::Serialize;
-use tokio::io::AsyncWrite;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio_util::codec::FramedRead;
 
 use std::cmp::Eq;
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::pin::Pin;
+use std::time::Duration;
 
 use super::Docker;
+use crate::batch::fan_out;
 use crate::errors::Error;
 
 use crate::models::*;
@@ -208,7 +212,9 @@ where
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_disabled: Option<bool>,
 
-    /// MAC address of the container.
+    /// MAC address of the container. Newer daemons prefer the per-network
+    /// [EndpointSettings::mac_address](EndpointSettings) field, this field is kept for older
+    /// daemons that only honor the container level setting.
     #[serde(rename = "MacAddress")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mac_address: Option<T>,
@@ -299,6 +305,15 @@ pub struct StopContainerOptions {
     pub t: i64,
 }
 
+/// Result type for the [Stop Container API](Docker::stop_container())
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopResult {
+    /// The container was stopped.
+    Stopped,
+    /// The container was already stopped.
+    AlreadyStopped,
+}
+
 /// Parameters used in the [Start Container API](Docker::start_container())
 ///
 /// ## Examples
@@ -341,7 +356,9 @@ pub struct RemoveContainerOptions {
     pub v: bool,
     /// If the container is running, kill it before removing it.
     pub force: bool,
-    /// Remove the specified link associated with the container.
+    /// Remove the specified link associated with the container, instead of the container itself.
+    /// The container name passed to the [Remove Container API](Docker::remove_container()) is then
+    /// the link, in the form `parent/alias`, and the linked container is left running.
     pub link: bool,
 }
 
@@ -375,7 +392,7 @@ pub struct AttachContainerResults {
 }
 
 impl fmt::Debug for AttachContainerResults {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "AttachContainerResults")
     }
 }
@@ -535,6 +552,10 @@ where
     /// Only return this number of log lines from the end of the logs. Specify as an integer or all
     /// to output `all` log lines.
     pub tail: T,
+    /// Stop the stream once this many bytes of log output have been returned, truncating the
+    /// last message to fit. This is applied by the client and not sent to the server.
+    #[serde(skip)]
+    pub max_bytes: Option<usize>,
 }
 
 /// Result type for the [Logs API](Docker::logs())
@@ -559,6 +580,17 @@ impl fmt::Display for LogOutput {
     }
 }
 
+impl AsRef<[u8]> for LogOutput {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            LogOutput::StdErr { message } => message.as_ref(),
+            LogOutput::StdOut { message } => message.as_ref(),
+            LogOutput::StdIn { message } => message.as_ref(),
+            LogOutput::Console { message } => message.as_ref(),
+        }
+    }
+}
+
 impl LogOutput {
     /// Get the raw bytes of the output
     pub fn into_bytes(self) -> Bytes {
@@ -569,6 +601,110 @@ impl LogOutput {
             LogOutput::Console { message } => message,
         }
     }
+
+    fn truncate(self, len: usize) -> LogOutput {
+        self.map_message(|mut message| {
+            message.truncate(len);
+            message
+        })
+    }
+
+    fn map_message<F>(self, f: F) -> LogOutput
+    where
+        F: FnOnce(Bytes) -> Bytes,
+    {
+        match self {
+            LogOutput::StdErr { message } => LogOutput::StdErr {
+                message: f(message),
+            },
+            LogOutput::StdOut { message } => LogOutput::StdOut {
+                message: f(message),
+            },
+            LogOutput::StdIn { message } => LogOutput::StdIn {
+                message: f(message),
+            },
+            LogOutput::Console { message } => LogOutput::Console {
+                message: f(message),
+            },
+        }
+    }
+}
+
+/// Position in the logs of a container, used by the [Logs Since Timestamp
+/// API](Docker::logs_since_timestamp())
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogsCursor {
+    /// Only return logs since this time, as a UNIX timestamp.
+    pub since: i64,
+    /// Number of lines with the `since` timestamp that were already returned. The `since` option
+    /// of [`LogsOptions`] only holds whole seconds, so these are skipped on the next call.
+    pub seen: usize,
+}
+
+/// Parse the UNIX timestamp, in whole seconds, of an RFC 3339 log timestamp in UTC such as
+/// `2021-01-02T03:04:05.123456789Z`. The daemon formats log timestamps in UTC, so timestamps
+/// with any other offset are rejected rather than misread.
+fn parse_log_timestamp(timestamp: &[u8]) -> Option<i64> {
+    let digits = |bytes: &[u8]| !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit);
+    let (datetime, fraction) = match timestamp {
+        [datetime @ .., b'Z'] if datetime.len() == 19 => (datetime, None),
+        [datetime @ .., b'Z'] if datetime.len() > 20 && datetime[19] == b'.' => {
+            (&datetime[..19], Some(&datetime[20..]))
+        }
+        _ => return None,
+    };
+    if !fraction.map(digits).unwrap_or(true)
+        || datetime.iter().enumerate().any(|(index, b)| match index {
+            4 | 7 => *b != b'-',
+            10 => *b != b'T',
+            13 | 16 => *b != b':',
+            _ => !b.is_ascii_digit(),
+        })
+    {
+        return None;
+    }
+
+    let number = |range: std::ops::Range<usize>| -> i64 {
+        datetime[range]
+            .iter()
+            .fold(0, |number, b| number * 10 + i64::from(b - b'0'))
+    };
+    let (year, month, day) = (number(0..4), number(5..7), number(8..10));
+    let (hour, minute, second) = (number(11..13), number(14..16), number(17..19));
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    // Days since the epoch of a proleptic Gregorian date.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// Host address of the first binding of a published TCP port, substituting the loopback address
+/// for a port bound to all interfaces.
+fn published_address(ports: &PortMap, container_port: u16) -> Option<SocketAddr> {
+    let bindings = ports.get(&format!("{}/tcp", container_port))?.as_ref()?;
+
+    bindings.iter().find_map(|binding| {
+        let port = binding.host_port.as_deref()?.parse().ok()?;
+        let ip = match binding.host_ip.as_deref() {
+            None | Some("") | Some("0.0.0.0") => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            Some("::") => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            Some(ip) => ip.parse().ok()?,
+        };
+        Some(SocketAddr::new(ip, port))
+    })
 }
 
 /// Parameters used in the [Stats API](super::Docker::stats())
@@ -758,7 +894,9 @@ pub struct Stats {
     pub preread: String,
     pub num_procs: u32,
     pub pids_stats: PidsStats,
+    /// Network statistics reported by older daemons, aggregated over all interfaces.
     pub network: Option<NetworkStats>,
+    /// Network statistics keyed by interface name, e.g. `eth0`.
     pub networks: Option<HashMap<String, NetworkStats>>,
     pub memory_stats: MemoryStats,
     pub blkio_stats: BlkioStats,
@@ -1127,6 +1265,24 @@ where
     pub path: T,
 }
 
+/// Stat information of a path in a container's filesystem, returned by the [Stat Container Path
+/// API](Docker::stat_container_path())
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPathStat {
+    /// Base name of the path.
+    pub name: String,
+    /// Size of the file in bytes.
+    pub size: i64,
+    /// File mode and permission bits, as a Go `os.FileMode`.
+    pub mode: u32,
+    /// Modification time, in RFC 3339 format.
+    pub mtime: String,
+    /// Target of the path, if it is a symbolic link.
+    #[serde(default)]
+    pub link_target: String,
+}
+
 impl Docker {
     /// ---
     ///
@@ -1163,7 +1319,7 @@ impl Docker {
     ///
     /// docker.list_containers(options);
     /// ```
-    pub async fn list_containers<'de, T>(
+    pub async fn list_containers<T>(
         &self,
         options: Option<ListContainersOptions<T>>,
     ) -> Result<Vec<ContainerSummary>, Error>
@@ -1243,7 +1399,8 @@ impl Docker {
     /// # Start Container
     ///
     /// Starts a container, after preparing it with the [Create Container
-    /// API](struct.Docker.html#method.create_container).
+    /// API](struct.Docker.html#method.create_container). Starting a container that is already
+    /// running succeeds as well.
     ///
     /// # Arguments
     ///
@@ -1296,7 +1453,7 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Stop Result](StopResult) enum, wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -1315,7 +1472,7 @@ impl Docker {
         &self,
         container_name: &str,
         options: Option<StopContainerOptions>,
-    ) -> Result<(), Error> {
+    ) -> Result<StopResult, Error> {
         let url = format!("/containers/{}/stop", container_name);
 
         let req = self.build_request(
@@ -1325,18 +1482,21 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_unit(req).await
+        match self.process_into_status(req).await? {
+            StatusCode::NOT_MODIFIED => Ok(StopResult::AlreadyStopped),
+            _ => Ok(StopResult::Stopped),
+        }
     }
 
     /// ---
     ///
     /// # Remove Container
     ///
-    /// Remove a container.
+    /// Remove a container, or with the `link` option set, a legacy link between two containers.
     ///
     /// # Arguments
     ///
-    /// - Container name as a string slice.
+    /// - Container name as a string slice, or the link as `parent/alias` when removing a link.
     /// - Optional [Remove Container Options](RemoveContainerOptions) struct.
     ///
     /// # Returns
@@ -1365,6 +1525,12 @@ impl Docker {
         container_name: &str,
         options: Option<RemoveContainerOptions>,
     ) -> Result<(), Error> {
+        // Links are named after their full path, e.g. `/parent/alias`, which would otherwise leave
+        // an empty segment in the URL.
+        let container_name = match options {
+            Some(RemoveContainerOptions { link: true, .. }) => container_name.trim_matches('/'),
+            _ => container_name,
+        };
         let url = format!("/containers/{}", container_name);
 
         let req = self.build_request(
@@ -1392,7 +1558,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [ContainerWaitResponse](ContainerWaitResponse), wrapped in a
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1444,6 +1610,58 @@ impl Docker {
         })
     }
 
+    /// ---
+    ///
+    /// # Wait Containers
+    ///
+    /// Wait for several containers to stop concurrently, using the [Wait Container
+    /// API](Docker::wait_container()).
+    ///
+    /// Every wait request is sent at once rather than through a bounded batch: a wait is an idle
+    /// long poll, and with the `next-exit` condition a container that stops before its queued
+    /// wait is sent would never be reported.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container names as a slice of string slices.
+    ///  - Optional [Wait Container Options](WaitContainerOptions) struct, applied to every
+    ///    container.
+    ///
+    /// # Returns
+    ///
+    ///  - A tuple of the container name and its [Wait Container Response](ContainerWaitResponse)
+    ///    in the order the containers stop, wrapped in a Stream. A non-zero exit code is reported
+    ///    as a [Docker Container Wait Error](Error::DockerContainerWaitError) carrying the code.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::WaitContainerOptions;
+    ///
+    /// let options = Some(WaitContainerOptions{
+    ///     condition: "not-running",
+    /// });
+    ///
+    /// docker.wait_containers(&["job-1", "job-2"], options);
+    /// ```
+    pub fn wait_containers<T>(
+        &self,
+        container_names: &[&str],
+        options: Option<WaitContainerOptions<T>>,
+    ) -> impl Stream<Item = (String, Result<ContainerWaitResponse, Error>)>
+    where
+        T: Into<String> + Serialize + Clone,
+    {
+        stream::select_all(container_names.iter().map(|container_name| {
+            let container_name = container_name.to_string();
+            self.wait_container(&container_name, options.clone())
+                .map(move |res| (container_name.clone(), res))
+        }))
+    }
+
     /// ---
     ///
     /// # Attach Container
@@ -1642,6 +1860,75 @@ impl Docker {
         self.process_into_value(req).await
     }
 
+    /// ---
+    ///
+    /// # Wait For Port
+    ///
+    /// Wait until a TCP port of a container is published and accepts connections on the host,
+    /// polling the [Inspect Container API](Docker::inspect_container()) for the port binding. A
+    /// port bound to all interfaces is connected to through the loopback address, so this is only
+    /// meaningful when the Docker daemon runs on the local host.
+    ///
+    /// A connection only proves that something listens on the host port. With the userland proxy
+    /// enabled, `docker-proxy` accepts connections as soon as the port is published, before the
+    /// application in the container listens, so a protocol level check may still be needed.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Container port, for example `5432`.
+    ///  - Maximum time to wait for the port.
+    ///
+    /// # Returns
+    ///
+    ///  - The host address the port is reachable on, wrapped in a Future. A [Port Wait Timeout
+    ///    Error](Error::PortWaitTimeoutError) is returned if the port does not become ready in time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::time::Duration;
+    ///
+    /// docker.wait_for_port("postgres", 5432, Duration::from_secs(30));
+    /// ```
+    pub async fn wait_for_port(
+        &self,
+        container_name: &str,
+        container_port: u16,
+        timeout: Duration,
+    ) -> Result<SocketAddr, Error> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+        let wait = async {
+            loop {
+                let inspect = self
+                    .inspect_container(container_name, None::<InspectContainerOptions>)
+                    .await?;
+                let address = inspect
+                    .network_settings
+                    .and_then(|settings| settings.ports)
+                    .and_then(|ports| published_address(&ports, container_port));
+
+                if let Some(address) = address {
+                    if tokio::net::TcpStream::connect(address).await.is_ok() {
+                        return Ok(address);
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::PortWaitTimeoutError {
+                container: String::from(container_name),
+                port: container_port,
+            }),
+        }
+    }
+
     /// ---
     ///
     /// # Top Processes
@@ -1704,7 +1991,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Log Output](LogOutput) enum, wrapped in a
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1732,6 +2019,7 @@ impl Docker {
         T: Into<String> + Serialize,
     {
         let url = format!("/containers/{}/logs", container_name);
+        let max_bytes = options.as_ref().and_then(|options| options.max_bytes);
 
         let req = self.build_request(
             &url,
@@ -1740,7 +2028,225 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_stream_string(req)
+        let stream = self.process_into_stream_string(req);
+
+        // Checking the budget before polling ends the stream, and drops the connection, as soon
+        // as it is used up rather than on the next log line.
+        Box::pin(stream::unfold(
+            (stream, max_bytes),
+            |(mut stream, remaining)| async move {
+                let item = match remaining {
+                    Some(0) => return None,
+                    Some(remaining) => stream
+                        .next()
+                        .await?
+                        .map(|output| output.truncate(remaining)),
+                    None => stream.next().await?,
+                };
+                let remaining = match &item {
+                    Ok(output) => remaining.map(|remaining| remaining - output.as_ref().len()),
+                    Err(_) => remaining,
+                };
+                Some((item, (stream, remaining)))
+            },
+        ))
+    }
+
+    /// ---
+    ///
+    /// # Logs Since Timestamp
+    ///
+    /// Fetch the `stdout` and `stderr` logs written since a [Logs Cursor](LogsCursor), for
+    /// incremental log shipping. The returned cursor points past the last returned line, so
+    /// passing it to the next call returns only newer lines.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Logs Cursor](LogsCursor) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A tuple of the [Log Output](LogOutput) lines, without their timestamp, and the new
+    ///    [Logs Cursor](LogsCursor), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::LogsCursor;
+    ///
+    /// async {
+    ///     let (lines, cursor) = docker
+    ///         .logs_since_timestamp("hello-world", LogsCursor::default())
+    ///         .await
+    ///         .unwrap();
+    ///     let (newer_lines, _) = docker.logs_since_timestamp("hello-world", cursor).await.unwrap();
+    /// };
+    /// ```
+    pub async fn logs_since_timestamp(
+        &self,
+        container_name: &str,
+        cursor: LogsCursor,
+    ) -> Result<(Vec<LogOutput>, LogsCursor), Error> {
+        let mut stream = self.logs(
+            container_name,
+            Some(LogsOptions::<String> {
+                stdout: true,
+                stderr: true,
+                since: cursor.since,
+                timestamps: true,
+                ..Default::default()
+            }),
+        );
+
+        // Console output is not framed per line, so split it and carry partial lines over.
+        let mut raw_lines = Vec::new();
+        let mut partial: Option<LogOutput> = None;
+        while let Some(output) = stream.next().await {
+            let output = output?;
+            let output = match partial.take() {
+                Some(prefix) if mem::discriminant(&prefix) == mem::discriminant(&output) => {
+                    prefix.map_message(|message| [message, output.into_bytes()].concat().into())
+                }
+                Some(prefix) => {
+                    raw_lines.push(prefix);
+                    output
+                }
+                None => output,
+            };
+
+            let mut start = 0;
+            for (end, _) in output
+                .as_ref()
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| **b == b'\n')
+            {
+                raw_lines.push(
+                    output
+                        .clone()
+                        .map_message(|message| message.slice(start..=end)),
+                );
+                start = end + 1;
+            }
+            if start < output.as_ref().len() {
+                partial = Some(output.map_message(|message| message.slice(start..)));
+            }
+        }
+        raw_lines.extend(partial);
+
+        let mut lines = Vec::new();
+        let mut next = LogsCursor { seen: 0, ..cursor };
+
+        for output in raw_lines {
+            let (timestamp, len) = match output.as_ref().iter().position(|b| *b == b' ') {
+                Some(len) => (parse_log_timestamp(&output.as_ref()[..len]), len + 1),
+                None => (None, 0),
+            };
+
+            match timestamp {
+                Some(timestamp) if timestamp == next.since => next.seen += 1,
+                Some(timestamp) => {
+                    next = LogsCursor {
+                        since: timestamp,
+                        seen: 1,
+                    }
+                }
+                None => (),
+            }
+            if timestamp == Some(cursor.since) && next.seen <= cursor.seen {
+                continue;
+            }
+
+            lines.push(output.map_message(|message| message.slice(len..)));
+        }
+
+        Ok((lines, next))
+    }
+
+    /// ---
+    ///
+    /// # Container Logs To Writer
+    ///
+    /// Stream a container's logs into writers, such as files or pipes, returning once the log
+    /// stream ends. Frames are demultiplexed so that `stdout` (and console output, for
+    /// containers with a TTY) goes to the first writer and `stderr` to the second one, or to
+    /// the first writer as well if no `stderr` writer is given. Both writers are flushed once
+    /// the stream ends.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Optional [Logs Options](LogsOptions) struct.
+    ///  - A mutable reference to an [AsyncWrite](tokio::io::AsyncWrite) for `stdout`.
+    ///  - An optional mutable reference to an [AsyncWrite](tokio::io::AsyncWrite) for `stderr`.
+    ///
+    /// # Returns
+    ///
+    ///  - A tuple of the number of bytes written to `stdout` and to `stderr`, wrapped in a
+    ///    Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::LogsOptions;
+    ///
+    /// let options = Some(LogsOptions::<String>{
+    ///     stdout: true,
+    ///     stderr: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// async {
+    ///     let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+    ///     docker
+    ///         .container_logs_to_writer("hello-world", options, &mut stdout, Some(&mut stderr))
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    pub async fn container_logs_to_writer<T, O, E>(
+        &self,
+        container_name: &str,
+        options: Option<LogsOptions<T>>,
+        stdout: &mut O,
+        mut stderr: Option<&mut E>,
+    ) -> Result<(u64, u64), Error>
+    where
+        T: Into<String> + Serialize,
+        O: AsyncWrite + Unpin,
+        E: AsyncWrite + Unpin,
+    {
+        let mut stream = self.logs(container_name, options);
+        let (mut stdout_written, mut stderr_written) = (0, 0);
+
+        while let Some(output) = stream.next().await {
+            match output? {
+                LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                    stdout.write_all(&message).await?;
+                    stdout_written += message.len() as u64;
+                }
+                LogOutput::StdErr { message } => {
+                    match stderr.as_mut() {
+                        Some(stderr) => stderr.write_all(&message).await?,
+                        None => stdout.write_all(&message).await?,
+                    }
+                    stderr_written += message.len() as u64;
+                }
+                LogOutput::StdIn { .. } => (),
+            }
+        }
+
+        stdout.flush().await?;
+        if let Some(stderr) = stderr {
+            stderr.flush().await?;
+        }
+
+        Ok((stdout_written, stderr_written))
     }
 
     /// ---
@@ -1756,7 +2262,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - An Option of Vector of [Container Change Response Item](ContainerChangeResponseItem) structs, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -1796,7 +2302,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Stats](Stats) struct, wrapped in a
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1813,6 +2319,45 @@ impl Docker {
     ///
     /// docker.stats("hello-world", options);
     /// ```
+    ///
+    /// A single sample, taken with `one_shot` disabled, also carries the previous CPU reading, so
+    /// the CPU usage can be computed the way `docker stats` does:
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::StatsOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async {
+    ///     let stats = docker
+    ///         .stats("hello-world", Some(StatsOptions { stream: false, one_shot: false }))
+    ///         .try_next()
+    ///         .await
+    ///         .unwrap()
+    ///         .unwrap();
+    ///
+    ///     let cpu_delta = stats.cpu_stats.cpu_usage.total_usage as f64
+    ///         - stats.precpu_stats.cpu_usage.total_usage as f64;
+    ///     let system_delta = stats.cpu_stats.system_cpu_usage.unwrap_or_default() as f64
+    ///         - stats.precpu_stats.system_cpu_usage.unwrap_or_default() as f64;
+    ///     let online_cpus = stats.cpu_stats.online_cpus.unwrap_or_else(|| {
+    ///         stats
+    ///             .cpu_stats
+    ///             .cpu_usage
+    ///             .percpu_usage
+    ///             .as_ref()
+    ///             .map_or(1, |usage| usage.len() as u64)
+    ///     });
+    ///
+    ///     let cpu_percent = if system_delta > 0.0 && cpu_delta > 0.0 {
+    ///         cpu_delta / system_delta * online_cpus as f64 * 100.0
+    ///     } else {
+    ///         0.0
+    ///     };
+    /// };
+    /// ```
     pub fn stats(
         &self,
         container_name: &str,
@@ -1892,7 +2437,10 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Container Update Response](ContainerUpdateResponse), wrapped in a Future. The daemon
+    ///    may accept a value it cannot fully apply, for example a kernel memory limit on cgroup
+    ///    v2, and reports that in the `warnings`. Use the [Inspect Container
+    ///    API](Docker::inspect_container()) to read back the applied limits.
     ///
     /// # Examples
     ///
@@ -1915,7 +2463,7 @@ impl Docker {
         &self,
         container_name: &str,
         config: UpdateContainerOptions<T>,
-    ) -> Result<(), Error>
+    ) -> Result<ContainerUpdateResponse, Error>
     where
         T: Into<String> + Eq + Hash + Serialize,
     {
@@ -1928,7 +2476,7 @@ impl Docker {
             Docker::serialize_payload(Some(config)),
         );
 
-        self.process_into_unit(req).await
+        self.process_into_value(req).await
     }
 
     /// ---
@@ -1980,6 +2528,139 @@ impl Docker {
         self.process_into_unit(req).await
     }
 
+    /// ---
+    ///
+    /// # Rename And Restart
+    ///
+    /// Rename a container, then restart another container. If the restart fails, the first
+    /// container is renamed back to its original name before the error is returned.
+    ///
+    /// The other container is restarted rather than started, so that a container that is already
+    /// running, such as a proxy, picks up the new name. Restarting a stopped container starts it.
+    ///
+    /// # Arguments
+    ///
+    ///  - Name of the container to rename as a string slice. This should be the container name
+    ///    rather than its ID, as it is used to restore the name on rollback.
+    ///  - [Rename Container Options](RenameContainerOptions) struct.
+    ///  - Name of the container to restart as a string slice.
+    ///  - Optional [Restart Container Options](RestartContainerOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::{RenameContainerOptions, RestartContainerOptions};
+    ///
+    /// let rename = RenameContainerOptions {
+    ///     name: "app-retired"
+    /// };
+    ///
+    /// docker.rename_and_restart("app", rename, "proxy", None::<RestartContainerOptions>);
+    /// ```
+    pub async fn rename_and_restart<T>(
+        &self,
+        container_name: &str,
+        options: RenameContainerOptions<T>,
+        restart_container_name: &str,
+        restart_options: Option<RestartContainerOptions>,
+    ) -> Result<(), Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let new_name: String = options.name.into();
+
+        self.rename_container(
+            container_name,
+            RenameContainerOptions {
+                name: new_name.as_str(),
+            },
+        )
+        .await?;
+
+        if let Err(e) = self
+            .restart_container(restart_container_name, restart_options)
+            .await
+        {
+            if let Err(rollback) = self
+                .rename_container(
+                    &new_name,
+                    RenameContainerOptions {
+                        name: container_name.trim_start_matches('/'),
+                    },
+                )
+                .await
+            {
+                warn!(
+                    "Failed to rename container {} back to {}: {}",
+                    new_name, container_name, rollback
+                );
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// ---
+    ///
+    /// # Restart All With Label
+    ///
+    /// Restart every running container matching a label filter, restarting at most `concurrency`
+    /// containers at a time. A failure to restart one container does not stop the others from
+    /// being restarted.
+    ///
+    /// # Arguments
+    ///
+    ///  - Label filter as a string slice, either `key` or `key=value`.
+    ///  - Optional [Restart Container Options](RestartContainerOptions) struct.
+    ///  - Maximum number of containers to restart concurrently.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of container IDs with the result of restarting each, wrapped in a Future. An
+    ///    error is only returned if the containers could not be listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::RestartContainerOptions;
+    ///
+    /// docker.restart_all_with_label("config-version=old", Some(RestartContainerOptions { t: 10 }), 4);
+    /// ```
+    pub async fn restart_all_with_label(
+        &self,
+        label: &str,
+        options: Option<RestartContainerOptions>,
+        concurrency: usize,
+    ) -> Result<Vec<(String, Result<(), Error>)>, Error> {
+        let mut filters = HashMap::new();
+        filters.insert("label", vec![label]);
+
+        let containers = self
+            .list_containers(Some(ListContainersOptions {
+                filters,
+                ..Default::default()
+            }))
+            .await?;
+
+        let ids = containers.into_iter().filter_map(|container| container.id);
+
+        Ok(fan_out(ids, concurrency, |id| async move {
+            let result = self.restart_container(&id, options).await;
+            (id, result)
+        })
+        .await)
+    }
+
     /// ---
     ///
     /// # Pause Container
@@ -2207,28 +2888,186 @@ impl Docker {
 
         self.process_into_body(req)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    #![cfg(not(target_arch = "windows"))]
-
-    use futures_util::TryStreamExt;
-    use yup_hyper_mock::HostToReplyConnector;
-
-    use crate::{Docker, API_DEFAULT_VERSION};
-
-    use super::WaitContainerOptions;
-
-    #[tokio::test]
-    async fn test_container_wait_with_error() {
-        let mut connector = HostToReplyConnector::default();
-        connector.m.insert(
-            String::from("http://127.0.0.1"),
-            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Error\":null,\"StatusCode\":1}".to_string(),
-        );
 
-        let docker =
+    /// ---
+    ///
+    /// # Stat Container Path
+    ///
+    /// Get information about a resource in the filesystem of container id, without downloading
+    /// it.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Download From Container Options](DownloadFromContainerOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Container Path Stat](ContainerPathStat), decoded from the
+    ///    `X-Docker-Container-Path-Stat` header and wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::DownloadFromContainerOptions;
+    ///
+    /// let options = DownloadFromContainerOptions{
+    ///     path: "/etc/hostname",
+    /// };
+    ///
+    /// docker.stat_container_path("my-container", options);
+    /// ```
+    pub async fn stat_container_path<T>(
+        &self,
+        container_name: &str,
+        options: DownloadFromContainerOptions<T>,
+    ) -> Result<ContainerPathStat, Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let url = format!("/containers/{}/archive", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::HEAD),
+            Some(options),
+            Ok(Body::empty()),
+        );
+
+        let headers = self.process_into_headers(req).await?;
+        let header = headers.get("X-Docker-Container-Path-Stat").ok_or_else(|| {
+            Error::ContainerPathStatError {
+                message: String::from("missing X-Docker-Container-Path-Stat header"),
+            }
+        })?;
+
+        let json =
+            base64::decode(header.as_bytes()).map_err(|e| Error::ContainerPathStatError {
+                message: e.to_string(),
+            })?;
+
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// ---
+    ///
+    /// # Export Container
+    ///
+    /// Export the contents of a container as a tarball.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - An uncompressed TAR archive, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.export_container("my-container");
+    /// ```
+    pub fn export_container(
+        &self,
+        container_name: &str,
+    ) -> impl Stream<Item = Result<Bytes, Error>> {
+        let url = format!("/containers/{}/export", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_body(req)
+    }
+
+    /// ---
+    ///
+    /// # Export Container To Writer
+    ///
+    /// Export the contents of a container as a tarball, streaming it into a writer such as a
+    /// file without buffering the archive in memory. The writer is flushed once the export
+    /// completes.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - A mutable reference to an [AsyncWrite](tokio::io::AsyncWrite).
+    ///
+    /// # Returns
+    ///
+    ///  - The number of bytes written, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async {
+    ///     let mut archive = Vec::new();
+    ///     docker.export_container_to_writer("my-container", &mut archive).await.unwrap();
+    /// };
+    /// ```
+    pub async fn export_container_to_writer<W>(
+        &self,
+        container_name: &str,
+        writer: &mut W,
+    ) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut stream = self.export_container(container_name);
+        let mut written = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{StreamExt, TryStreamExt};
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::{
+        parse_log_timestamp, published_address, Config, DownloadFromContainerOptions,
+        InspectContainerOptions, ListContainersOptions, LogsCursor, LogsOptions,
+        RemoveContainerOptions, RenameContainerOptions, ResizeContainerTtyOptions,
+        RestartContainerOptions, StartContainerOptions, StopResult, UpdateContainerOptions,
+        WaitContainerOptions,
+    };
+    use crate::models::{
+        ContainerChangeResponseItem, ContainerStateStatusEnum, DeviceRequest, HealthConfig,
+        HealthStatusEnum, HostConfig, PortBinding, RestartPolicy, RestartPolicyNameEnum,
+    };
+    use crate::test_server;
+
+    #[tokio::test]
+    async fn test_container_wait_with_error() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Error\":null,\"StatusCode\":1}".to_string(),
+        );
+
+        let docker =
             Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
                 .unwrap();
 
@@ -2242,4 +3081,768 @@ mod tests {
             Err(crate::errors::Error::DockerContainerWaitError { code: _, error: _ })
         ));
     }
+
+    #[tokio::test]
+    async fn test_list_containers_mounts() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n[\
+            {\"Id\":\"a\",\"Mounts\":[{\"Type\":\"volume\",\"Name\":\"pgdata\",\"Source\":\"/var/lib/docker/volumes/pgdata/_data\",\"Destination\":\"/var/lib/postgresql/data\",\"Driver\":\"local\",\"Mode\":\"z\",\"RW\":true,\"Propagation\":\"\"}]},\
+            {\"Id\":\"b\"}]"
+                .to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let containers = docker
+            .list_containers(None::<ListContainersOptions<String>>)
+            .await
+            .unwrap();
+
+        let mounts = containers[0].mounts.as_ref().unwrap();
+        assert_eq!(mounts[0].name.as_deref(), Some("pgdata"));
+        assert_eq!(
+            mounts[0].destination.as_deref(),
+            Some("/var/lib/postgresql/data")
+        );
+        assert_eq!(mounts[0].rw, Some(true));
+        assert_eq!(containers[1].mounts, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_container_warnings() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Warnings\":[\"Your kernel does not support memory swappiness capabilities or the cgroup is not mounted. Memory swappiness discarded.\"]}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .update_container(
+                "postgres",
+                UpdateContainerOptions::<String> {
+                    memory: Some(314572800),
+                    memory_swappiness: Some(10),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.warnings.map(|warnings| warnings.len()), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_restart_all_with_label() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n[{\"Id\":\"a\"},{\"Id\":\"b\"}]".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let mut results = docker
+            .restart_all_with_label("config-version=old", None, 2)
+            .await
+            .unwrap();
+        results.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|(id, result)| (id.as_str(), result.is_ok()))
+                .collect::<Vec<_>>(),
+            vec![("a", true), ("b", true)]
+        );
+    }
+
+    #[test]
+    fn test_published_address() {
+        let mut ports = HashMap::new();
+        ports.insert(String::from("6379/tcp"), None);
+        ports.insert(
+            String::from("5432/tcp"),
+            Some(vec![PortBinding {
+                host_ip: Some(String::from("0.0.0.0")),
+                host_port: Some(String::from("49153")),
+            }]),
+        );
+
+        assert_eq!(
+            published_address(&ports, 5432),
+            Some("127.0.0.1:49153".parse().unwrap())
+        );
+        assert_eq!(published_address(&ports, 6379), None);
+        assert_eq!(published_address(&ports, 80), None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_port_timeout() {
+        let (docker, _) = test_server::serve(|_| {
+            (
+                "200 OK",
+                String::from("{\"Id\":\"abc\",\"NetworkSettings\":{\"Ports\":{}}}"),
+            )
+        })
+        .await;
+
+        let result = docker
+            .wait_for_port("postgres", 5432, Duration::from_millis(300))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::errors::Error::PortWaitTimeoutError { ref container, port: 5432 })
+                if container == "postgres"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remove_container_link() {
+        let (docker, requests) = test_server::serve(|_| ("204 No Content", String::new())).await;
+
+        let result = docker
+            .remove_container(
+                "/webapp/db",
+                Some(RemoveContainerOptions {
+                    link: true,
+                    ..Default::default()
+                }),
+            )
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![format!(
+                "DELETE /v{}/containers/webapp/db?v=false&force=false&link=true",
+                API_DEFAULT_VERSION
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_container_healthcheck() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"abc\",\"Config\":{\"Image\":\"hello-world\",\"Healthcheck\":{\"Test\":[\"CMD-SHELL\",\"exit 0\"],\"Interval\":30000000000,\"Timeout\":5000000000,\"Retries\":3,\"StartPeriod\":1000000}}}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .inspect_container("abc", None::<InspectContainerOptions>)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.config.unwrap().healthcheck,
+            Some(HealthConfig {
+                test: Some(vec![String::from("CMD-SHELL"), String::from("exit 0")]),
+                interval: Some(Duration::from_secs(30)),
+                timeout: Some(Duration::from_secs(5)),
+                retries: Some(3),
+                start_period: Some(Duration::from_millis(1)),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_container_changes() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n[{\"Path\":\"/tmp\",\"Kind\":0},{\"Path\":\"/tmp/created\",\"Kind\":1}]".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker.container_changes("changes_test").await.unwrap();
+
+        assert_eq!(
+            result,
+            Some(vec![
+                ContainerChangeResponseItem {
+                    path: String::from("/tmp"),
+                    kind: 0,
+                },
+                ContainerChangeResponseItem {
+                    path: String::from("/tmp/created"),
+                    kind: 1,
+                },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_container_state() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"abc\",\"Name\":\"/web\",\"RestartCount\":2,\"SizeRw\":12,\"SizeRootFs\":2048,\"State\":{\"Status\":\"running\",\"Running\":true,\"Paused\":false,\"OOMKilled\":false,\"Pid\":42,\"ExitCode\":0,\"Health\":{\"Status\":\"healthy\",\"FailingStreak\":0}},\"NetworkSettings\":{\"Networks\":{\"bridge\":{\"IPAddress\":\"172.17.0.2\"}}}}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .inspect_container("abc", Some(InspectContainerOptions { size: true }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.name.as_deref(), Some("/web"));
+        assert_eq!(result.restart_count, Some(2));
+        assert_eq!(
+            (result.size_rw, result.size_root_fs),
+            (Some(12), Some(2048))
+        );
+
+        let state = result.state.unwrap();
+        assert_eq!(state.status, Some(ContainerStateStatusEnum::RUNNING));
+        assert_eq!(state.running, Some(true));
+        assert_eq!(state.oom_killed, Some(false));
+        assert_eq!(state.pid, Some(42));
+        assert_eq!(
+            state.health.and_then(|health| health.status),
+            Some(HealthStatusEnum::HEALTHY)
+        );
+
+        assert_eq!(
+            result
+                .network_settings
+                .and_then(|settings| settings.networks)
+                .and_then(|mut networks| networks.remove("bridge"))
+                .and_then(|endpoint| endpoint.ip_address)
+                .as_deref(),
+            Some("172.17.0.2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_container_security_opt() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"abc\",\"AppArmorProfile\":\"docker-default\",\"HostConfig\":{\"SecurityOpt\":[\"seccomp=/etc/docker/seccomp.json\",\"no-new-privileges\"]}}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .inspect_container("abc", None::<InspectContainerOptions>)
+            .await
+            .unwrap();
+
+        assert_eq!(result.app_armor_profile.as_deref(), Some("docker-default"));
+        assert_eq!(
+            result.host_config.unwrap().security_opt,
+            Some(vec![
+                String::from("seccomp=/etc/docker/seccomp.json"),
+                String::from("no-new-privileges"),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_container_log_path() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"abc\",\"LogPath\":\"/var/lib/docker/containers/abc/abc-json.log\",\"HostConfig\":{\"LogConfig\":{\"Type\":\"json-file\",\"Config\":{}}}}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .inspect_container("abc", None::<InspectContainerOptions>)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.log_path.as_deref(),
+            Some("/var/lib/docker/containers/abc/abc-json.log")
+        );
+    }
+
+    #[test]
+    fn test_config_anonymous_volumes() {
+        let mut volumes = HashMap::new();
+        volumes.insert("/data", HashMap::new());
+
+        let config = Config {
+            volumes: Some(volumes),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"Volumes":{"/data":{}}}"#
+        );
+    }
+
+    #[test]
+    fn test_config_host_config() {
+        let mut exposed_ports = HashMap::new();
+        exposed_ports.insert("80/tcp", HashMap::new());
+
+        let mut port_bindings = HashMap::new();
+        port_bindings.insert(
+            String::from("80/tcp"),
+            Some(vec![PortBinding {
+                host_ip: None,
+                host_port: Some(String::from("8080")),
+            }]),
+        );
+
+        let config = Config {
+            image: Some("nginx"),
+            exposed_ports: Some(exposed_ports),
+            host_config: Some(HostConfig {
+                port_bindings: Some(port_bindings),
+                binds: Some(vec![String::from("/srv/www:/usr/share/nginx/html:ro")]),
+                memory: Some(64 * 1024 * 1024),
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::UNLESS_STOPPED),
+                    maximum_retry_count: None,
+                }),
+                auto_remove: Some(false),
+                network_mode: Some(String::from("bridge")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::json!({
+                "Image": "nginx",
+                "ExposedPorts": { "80/tcp": {} },
+                "HostConfig": {
+                    "PortBindings": { "80/tcp": [{ "HostPort": "8080" }] },
+                    "Binds": ["/srv/www:/usr/share/nginx/html:ro"],
+                    "Memory": 67108864,
+                    "RestartPolicy": { "Name": "unless-stopped" },
+                    "AutoRemove": false,
+                    "NetworkMode": "bridge",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_resize_container_tty_options() {
+        let options = ResizeContainerTtyOptions {
+            width: 80,
+            height: 24,
+        };
+
+        assert_eq!(serde_urlencoded::to_string(options).unwrap(), "w=80&h=24");
+    }
+
+    #[test]
+    fn test_update_container_options() {
+        let options = UpdateContainerOptions {
+            memory: Some(314572800),
+            memory_swap: Some(314572800),
+            cpu_shares: Some(512),
+            cpu_period: Some(100000),
+            cpu_quota: Some(50000),
+            cpuset_cpus: Some("0-1"),
+            blkio_weight: Some(300),
+            restart_policy: Some(RestartPolicy {
+                name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                maximum_retry_count: Some(3),
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            serde_json::json!({
+                "Memory": 314572800,
+                "MemorySwap": 314572800,
+                "CpuShares": 512,
+                "CpuPeriod": 100000,
+                "CpuQuota": 50000,
+                "CpusetCpus": "0-1",
+                "BlkioWeight": 300,
+                "RestartPolicy": { "Name": "on-failure", "MaximumRetryCount": 3 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_config_user_and_group_add() {
+        let config = Config {
+            user: Some("1000:1000"),
+            host_config: Some(HostConfig {
+                group_add: Some(vec![String::from("docker")]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"User":"1000:1000","HostConfig":{"GroupAdd":["docker"]}}"#
+        );
+    }
+
+    #[test]
+    fn test_host_config_cgroup() {
+        let host_config = HostConfig {
+            cgroup_parent: Some(String::from("/system.slice/app.slice")),
+            cgroup: Some(String::from("container:abc")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&host_config).unwrap(),
+            serde_json::json!({
+                "CgroupParent": "/system.slice/app.slice",
+                "Cgroup": "container:abc",
+            })
+        );
+    }
+
+    #[test]
+    fn test_host_config_device_requests() {
+        // Equivalent of `docker run --gpus all`.
+        let host_config = HostConfig {
+            device_requests: Some(vec![DeviceRequest {
+                driver: Some(String::from("nvidia")),
+                count: Some(-1),
+                capabilities: Some(vec![vec![String::from("gpu")]]),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&host_config).unwrap(),
+            serde_json::json!({
+                "DeviceRequests": [{
+                    "Driver": "nvidia",
+                    "Count": -1,
+                    "Capabilities": [["gpu"]],
+                }],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_max_bytes() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:text/plain\r\n\r\nfirst\nsecond\nthird\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .logs(
+                "logs_test",
+                Some(LogsOptions::<String> {
+                    stdout: true,
+                    max_bytes: Some(10),
+                    ..Default::default()
+                }),
+            )
+            .map_ok(|output| output.into_bytes())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(result.concat(), b"first\nseco");
+    }
+
+    #[tokio::test]
+    async fn test_container_logs_to_writer() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/vnd.docker.raw-stream\r\n\r\n\u{1}\0\0\0\0\0\0\u{4}out\n\u{2}\0\0\0\0\0\0\u{4}err\n\u{1}\0\0\0\0\0\0\u{5}more\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let options = LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        };
+
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let written = docker
+            .container_logs_to_writer(
+                "logs_test",
+                Some(options.clone()),
+                &mut stdout,
+                Some(&mut stderr),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(written, (9, 4));
+        assert_eq!(stdout, b"out\nmore\n");
+        assert_eq!(stderr, b"err\n");
+
+        let mut combined = Vec::new();
+        docker
+            .container_logs_to_writer(
+                "logs_test",
+                Some(options),
+                &mut combined,
+                None::<&mut Vec<u8>>,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(combined, b"out\nerr\nmore\n");
+    }
+
+    #[tokio::test]
+    async fn test_wait_containers() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Error\":null,\"StatusCode\":0}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let mut result = docker
+            .wait_containers(&["job-1", "job-2"], None::<WaitContainerOptions<String>>)
+            .map(|(name, res)| (name, res.unwrap().status_code))
+            .collect::<Vec<_>>()
+            .await;
+        result.sort();
+
+        assert_eq!(
+            result,
+            vec![(String::from("job-1"), 0), (String::from("job-2"), 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_and_restart_rolls_back() {
+        let (docker, requests) = test_server::serve(|request| {
+            if request.contains("/restart") {
+                (
+                    "500 Internal Server Error",
+                    String::from("{\"message\":\"cannot restart proxy\"}"),
+                )
+            } else {
+                ("204 No Content", String::new())
+            }
+        })
+        .await;
+
+        let err = docker
+            .rename_and_restart(
+                "app",
+                RenameContainerOptions {
+                    name: "app-retired",
+                },
+                "proxy",
+                None::<RestartContainerOptions>,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            crate::errors::Error::DockerResponseServerError { status_code: 500, ref message }
+                if message == "cannot restart proxy"
+        ));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                format!(
+                    "POST /v{}/containers/app/rename?name=app-retired",
+                    API_DEFAULT_VERSION
+                ),
+                format!("POST /v{}/containers/proxy/restart", API_DEFAULT_VERSION),
+                format!(
+                    "POST /v{}/containers/app-retired/rename?name=app",
+                    API_DEFAULT_VERSION
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_container_to_writer() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/x-tar\r\nContent-Length:11\r\n\r\ntar-content".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let mut archive = Vec::new();
+        let written = docker
+            .export_container_to_writer("export_test", &mut archive)
+            .await
+            .unwrap();
+
+        assert_eq!(written, 11);
+        assert_eq!(archive, b"tar-content");
+    }
+
+    #[tokio::test]
+    async fn test_stat_container_path() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nX-Docker-Container-Path-Stat:eyJuYW1lIjoiaG9zdG5hbWUiLCJzaXplIjoxMywibW9kZSI6NDIwLCJtdGltZSI6IjIwMjItMDYtMDFUMTA6MDA6MDBaIiwibGlua1RhcmdldCI6IiJ9\r\nContent-Length:0\r\n\r\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let stat = docker
+            .stat_container_path(
+                "stat_test",
+                DownloadFromContainerOptions {
+                    path: "/etc/hostname",
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(stat.name, "hostname");
+        assert_eq!(stat.size, 13);
+        assert_eq!(stat.mode, 0o644);
+        assert_eq!(stat.mtime, "2022-06-01T10:00:00Z");
+        assert_eq!(stat.link_target, "");
+    }
+
+    #[tokio::test]
+    async fn test_stop_container_already_stopped() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 304 Not Modified\r\nServer:mock1\r\n\r\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker.stop_container("stop_test", None).await.unwrap();
+
+        assert_eq!(result, StopResult::AlreadyStopped);
+    }
+
+    #[tokio::test]
+    async fn test_start_container_already_started() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 304 Not Modified\r\nServer:mock1\r\n\r\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .start_container("start_test", None::<StartContainerOptions<String>>)
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_log_timestamp() {
+        assert_eq!(parse_log_timestamp(b"1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_log_timestamp(b"2021-03-04T05:06:07.123456789Z"),
+            Some(1_614_834_367)
+        );
+        assert_eq!(
+            parse_log_timestamp(b"2021-03-04T05:06:07.999999999Z"),
+            Some(1_614_834_367)
+        );
+        assert_eq!(parse_log_timestamp(b"2021-03-04T05:06:07.Z"), None);
+        assert_eq!(parse_log_timestamp(b"2021-03-04T05:06:07"), None);
+        assert_eq!(parse_log_timestamp(b"2021-03-04T06:06:07.1+01:00"), None);
+        assert_eq!(parse_log_timestamp(b"not a timestamp"), None);
+    }
+
+    #[tokio::test]
+    async fn test_logs_since_timestamp() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:text/plain\r\n\r\n\
+            2021-03-04T05:06:07.1Z old\n\
+            2021-03-04T05:06:07.2Z new\n\
+            2021-03-04T05:06:08.3Z newer\n"
+                .to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let (lines, cursor) = docker
+            .logs_since_timestamp(
+                "logs_test",
+                LogsCursor {
+                    since: 1_614_834_367,
+                    seen: 1,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            lines
+                .into_iter()
+                .map(|line| line.into_bytes())
+                .collect::<Vec<_>>()
+                .concat(),
+            b"new\nnewer\n"
+        );
+        assert_eq!(
+            cursor,
+            LogsCursor {
+                since: 1_614_834_368,
+                seen: 1,
+            }
+        );
+    }
 }
@@ -17,7 +17,7 @@ use futures_core::Stream;
 use futures_util::future::FutureExt;
 use futures_util::future::TryFutureExt;
 use futures_util::stream::TryStreamExt;
-use http::header::CONTENT_TYPE;
+use http::header::{HeaderMap, CONTENT_TYPE, USER_AGENT};
 use http::request::Builder;
 use hyper::client::{Client, HttpConnector};
 use hyper::{self, body::Bytes, Body, Method, Request, Response, StatusCode};
@@ -261,6 +261,7 @@ pub struct Docker {
     pub(crate) client_addr: String,
     pub(crate) client_timeout: u64,
     pub(crate) version: Arc<(AtomicUsize, AtomicUsize)>,
+    pub(crate) user_agent: Option<String>,
 }
 
 impl Clone for Docker {
@@ -271,6 +272,7 @@ impl Clone for Docker {
             client_addr: self.client_addr.clone(),
             client_timeout: self.client_timeout,
             version: self.version.clone(),
+            user_agent: self.user_agent.clone(),
         }
     }
 }
@@ -367,6 +369,13 @@ impl rustls::client::ResolvesClientCert for DockerClientCertResolver {
     }
 }
 
+/// Whether a `DOCKER_TLS_VERIFY` value asks for the server certificate to be verified, which the
+/// docker CLI does for any non-empty value.
+#[cfg(feature = "ssl")]
+fn tls_verify_enabled(value: Option<&str>) -> bool {
+    !value.unwrap_or_default().is_empty()
+}
+
 /// A Docker implementation typed to connect to a secure HTTPS connection using the `rustls`
 /// library.
 #[cfg(feature = "ssl")]
@@ -378,9 +387,17 @@ impl Docker {
     ///  - The connection url is sourced from the `DOCKER_HOST` environment variable.
     ///  - The certificate directory is sourced from the `DOCKER_CERT_PATH` environment variable.
     ///  - Certificates are named `key.pem`, `cert.pem` and `ca.pem` to indicate the private key,
-    ///  the server certificate and the certificate chain respectively.
+    ///    the server certificate and the certificate chain respectively.
+    ///  - The `DOCKER_TLS_VERIFY` environment variable has to be set to a non-empty value, which
+    ///    is how the docker CLI is told to verify the server certificate. This client cannot
+    ///    connect without verifying it, so an [SSL Verify Disabled
+    ///    Error](Error::SslVerifyDisabledError) is returned otherwise. Use
+    ///    [connect_with_ssl](Docker::connect_with_ssl()) to connect without reading the
+    ///    environment.
     ///  - The number of threads used for the HTTP connection pool defaults to 1.
     ///  - The request timeout defaults to 2 minutes.
+    ///  - Every request is sent under the [API_DEFAULT_VERSION](API_DEFAULT_VERSION), which a
+    ///    server older than that version rejects. See [negotiate_version](Docker::negotiate_version()).
     ///
     /// # Examples
     ///
@@ -394,6 +411,10 @@ impl Docker {
     ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
     pub fn connect_with_ssl_defaults() -> Result<Docker, Error> {
+        if !tls_verify_enabled(env::var("DOCKER_TLS_VERIFY").ok().as_deref()) {
+            return Err(SslVerifyDisabledError);
+        }
+
         let cert_path = DockerClientCertResolver::default_cert_path()?;
         Docker::connect_with_ssl(
             if let Ok(ref host) = env::var("DOCKER_HOST") {
@@ -418,7 +439,8 @@ impl Docker {
     ///  - `ssl_cert`: the server certificate path.
     ///  - `ssl_ca`: the certificate chain path.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
-    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `client_version`: the client version to communicate with the server. Every request is
+    ///    sent under this API version, which a server older than that version rejects.
     ///
     /// # Examples
     ///
@@ -515,6 +537,7 @@ impl Docker {
                 AtomicUsize::new(client_version.major_version),
                 AtomicUsize::new(client_version.minor_version),
             )),
+            user_agent: None,
         };
 
         Ok(docker)
@@ -528,9 +551,11 @@ impl Docker {
     /// # Defaults
     ///
     ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, and defaults
-    ///  to `localhost:2375`.
+    ///    to `localhost:2375`.
     ///  - The number of threads used for the HTTP connection pool defaults to 1.
     ///  - The request timeout defaults to 2 minutes.
+    ///  - Every request is sent under the [API_DEFAULT_VERSION](API_DEFAULT_VERSION), which a
+    ///    server older than that version rejects. See [negotiate_version](Docker::negotiate_version()).
     ///
     /// # Examples
     ///
@@ -554,7 +579,8 @@ impl Docker {
     ///
     ///  - `addr`: connection url including scheme and port.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
-    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `client_version`: the client version to communicate with the server. Every request is
+    ///    sent under this API version, which a server older than that version rejects.
     ///
     /// # Examples
     ///
@@ -591,6 +617,7 @@ impl Docker {
                 AtomicUsize::new(client_version.major_version),
                 AtomicUsize::new(client_version.minor_version),
             )),
+            user_agent: None,
         };
 
         Ok(docker)
@@ -602,8 +629,10 @@ impl Docker {
     /// # Defaults
     ///
     ///  - The unix socket location defaults to `/var/run/docker.sock`. The windows named pipe
-    ///  location defaults to `//./pipe/docker_engine`.
+    ///    location defaults to `//./pipe/docker_engine`.
     ///  - The request timeout defaults to 2 minutes.
+    ///  - Every request is sent under the [API_DEFAULT_VERSION](API_DEFAULT_VERSION), which a
+    ///    server older than that version rejects. See [negotiate_version](Docker::negotiate_version()).
     ///
     /// # Examples
     ///
@@ -630,7 +659,8 @@ impl Docker {
     ///
     ///  - `path`: connection unix socket path or windows named pipe path.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
-    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `client_version`: the client version to communicate with the server. Every request is
+    ///    sent under this API version, which a server older than that version rejects.
     ///
     /// # Examples
     ///
@@ -666,6 +696,8 @@ impl Docker {
     ///  - The socket location defaults to the value of `DEFAULT_SOCKET` env if its set and the URL
     ///    has `unix` scheme; otherwise `/var/run/docker.sock`.
     ///  - The request timeout defaults to 2 minutes.
+    ///  - Every request is sent under the [API_DEFAULT_VERSION](API_DEFAULT_VERSION), which a
+    ///    server older than that version rejects. See [negotiate_version](Docker::negotiate_version()).
     ///
     /// # Examples
     ///
@@ -697,7 +729,8 @@ impl Docker {
     ///
     ///  - `addr`: connection socket path.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
-    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `client_version`: the client version to communicate with the server. Every request is
+    ///    sent under this API version, which a server older than that version rejects.
     ///
     /// # Examples
     ///
@@ -732,6 +765,7 @@ impl Docker {
                 AtomicUsize::new(client_version.major_version),
                 AtomicUsize::new(client_version.minor_version),
             )),
+            user_agent: None,
         };
 
         Ok(docker)
@@ -747,8 +781,11 @@ impl Docker {
     ///
     /// # Defaults
     ///
-    ///  - The socket location defaults to `//./pipe/docker_engine`.
+    ///  - The socket location defaults to the value of `DOCKER_HOST` env if its set and the URL
+    ///    has `npipe` scheme; otherwise `//./pipe/docker_engine`.
     ///  - The request timeout defaults to 2 minutes.
+    ///  - Every request is sent under the [API_DEFAULT_VERSION](API_DEFAULT_VERSION), which a
+    ///    server older than that version rejects. See [negotiate_version](Docker::negotiate_version()).
     ///
     /// # Examples
     ///
@@ -762,7 +799,11 @@ impl Docker {
     ///
     /// ```
     pub fn connect_with_named_pipe_defaults() -> Result<Docker, Error> {
-        Docker::connect_with_named_pipe(DEFAULT_NAMED_PIPE, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+        let pipe_path = env::var("DOCKER_HOST")
+            .ok()
+            .filter(|p| p.starts_with("npipe://"));
+        let path = pipe_path.as_deref().unwrap_or(DEFAULT_NAMED_PIPE);
+        Docker::connect_with_named_pipe(path, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
     }
 
     /// Connect using a Windows Named Pipe.
@@ -771,7 +812,8 @@ impl Docker {
     ///
     ///  - `addr`: socket location.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
-    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `client_version`: the client version to communicate with the server. Every request is
+    ///    sent under this API version, which a server older than that version rejects.
     ///
     /// # Examples
     ///
@@ -808,6 +850,7 @@ impl Docker {
                 AtomicUsize::new(client_version.major_version),
                 AtomicUsize::new(client_version.minor_version),
             )),
+            user_agent: None,
         };
 
         Ok(docker)
@@ -858,7 +901,8 @@ impl Docker {
     ///  - `connector`: a `HostToReplyConnector` as defined in `yup_hyper_mock`
     ///  - `client_addr`: location to connect to.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
-    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `client_version`: the client version to communicate with the server. Every request is
+    ///    sent under this API version, which a server older than that version rejects.
     ///
     /// # Examples
     ///
@@ -902,13 +946,146 @@ impl Docker {
                 AtomicUsize::new(client_version.major_version),
                 AtomicUsize::new(client_version.minor_version),
             )),
+            user_agent: None,
         };
 
         Ok(docker)
     }
 }
 
+/// A builder for a [Docker] client, created with [Docker::builder()].
+///
+/// The transport is chosen from the scheme of the address:
+///  - `unix://` connects to a Unix socket.
+///  - `npipe://` connects to a Windows named pipe.
+///  - `tcp://` or `http://` connect over plain HTTP, unless SSL certificates are configured.
+///  - `https://` connects over SSL, which requires the `ssl` feature and the SSL certificates to
+///    be configured.
+///
+/// Without an address, the local Unix socket or Windows named pipe is used, or
+/// `localhost:2375` over plain HTTP on other platforms. SSL certificates cannot be used without
+/// an address.
+///
+/// There is no SSH transport, and failed requests are not retried: both need support in the
+/// client itself rather than in the builder.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use bollard::Docker;
+///
+/// use std::time::Duration;
+///
+/// let docker = Docker::builder()
+///     .addr("tcp://localhost:2375")
+///     .timeout(Duration::from_secs(30))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DockerBuilder {
+    addr: Option<String>,
+    timeout: Duration,
+    client_version: ClientVersion,
+    user_agent: Option<String>,
+    #[cfg(feature = "ssl")]
+    ssl: Option<(PathBuf, PathBuf, PathBuf)>,
+}
+
+impl DockerBuilder {
+    /// Set the address of the Docker daemon.
+    pub fn addr(mut self, addr: &str) -> Self {
+        self.addr = Some(addr.to_string());
+        self
+    }
+
+    /// Set the request timeout. By default, 2 minutes. The client counts the timeout in whole
+    /// seconds, so a fraction of a second is rounded up.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the client version to communicate with the server. By default,
+    /// [API_DEFAULT_VERSION](API_DEFAULT_VERSION).
+    pub fn client_version(mut self, client_version: &ClientVersion) -> Self {
+        self.client_version = *client_version;
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request. By default, no `User-Agent` header is
+    /// sent.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Connect over SSL, using the given key, certificate and certificate authority files.
+    #[cfg(feature = "ssl")]
+    pub fn ssl(mut self, ssl_key: &Path, ssl_cert: &Path, ssl_ca: &Path) -> Self {
+        self.ssl = Some((ssl_key.to_owned(), ssl_cert.to_owned(), ssl_ca.to_owned()));
+        self
+    }
+
+    /// Build the [Docker] client.
+    pub fn build(self) -> Result<Docker, Error> {
+        let timeout = self.timeout.as_secs() + u64::from(self.timeout.subsec_nanos() > 0);
+        let client_version = &self.client_version;
+
+        let docker = match self.addr.as_deref() {
+            #[cfg(feature = "ssl")]
+            Some(addr) if self.ssl.is_some() => {
+                let (ssl_key, ssl_cert, ssl_ca) = self.ssl.as_ref().unwrap();
+                Docker::connect_with_ssl(addr, ssl_key, ssl_cert, ssl_ca, timeout, client_version)
+            }
+            #[cfg(feature = "ssl")]
+            Some(addr) if addr.starts_with("https://") => Err(MissingSslCertificatesError {
+                addr: addr.to_string(),
+            }),
+            #[cfg(not(feature = "ssl"))]
+            Some(addr) if addr.starts_with("https://") => Err(SslFeatureDisabledError {
+                addr: addr.to_string(),
+            }),
+            #[cfg(unix)]
+            Some(addr) if addr.starts_with("unix://") => {
+                Docker::connect_with_unix(addr, timeout, client_version)
+            }
+            #[cfg(windows)]
+            Some(addr) if addr.starts_with("npipe://") => {
+                Docker::connect_with_named_pipe(addr, timeout, client_version)
+            }
+            Some(addr) => Docker::connect_with_http(addr, timeout, client_version),
+            #[cfg(feature = "ssl")]
+            None if self.ssl.is_some() => Err(MissingSslAddressError),
+            #[cfg(unix)]
+            None => Docker::connect_with_unix(DEFAULT_SOCKET, timeout, client_version),
+            #[cfg(windows)]
+            None => Docker::connect_with_named_pipe(DEFAULT_NAMED_PIPE, timeout, client_version),
+            #[cfg(not(any(unix, windows)))]
+            None => Docker::connect_with_http(DEFAULT_DOCKER_HOST, timeout, client_version),
+        }?;
+
+        Ok(Docker {
+            user_agent: self.user_agent,
+            ..docker
+        })
+    }
+}
+
 impl Docker {
+    /// Create a [Docker Builder](DockerBuilder) to configure the transport, timeout, client
+    /// version and user agent of a new client.
+    pub fn builder() -> DockerBuilder {
+        DockerBuilder {
+            addr: None,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+            client_version: *API_DEFAULT_VERSION,
+            user_agent: None,
+            #[cfg(feature = "ssl")]
+            ssl: None,
+        }
+    }
+
     /// Set the request timeout.
     ///
     /// This timeout is shared by all requests to the Docker Engine API.
@@ -989,6 +1166,22 @@ impl Docker {
         }
     }
 
+    pub(crate) fn process_into_status(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<StatusCode, Error>> {
+        let fut = self.process_request(req);
+        async move { Ok(fut.await?.status()) }
+    }
+
+    pub(crate) fn process_into_headers(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<HeaderMap, Error>> {
+        let fut = self.process_request(req);
+        async move { Ok(fut.await?.into_parts().0.headers) }
+    }
+
     pub(crate) fn process_into_body(
         &self,
         req: Result<Request<Body>, Error>,
@@ -1043,6 +1236,56 @@ impl Docker {
         self.version.as_ref().into()
     }
 
+    /// Return a client that issues its requests with the given API version, leaving the version
+    /// of this client untouched. The returned client shares the underlying connection, so this is
+    /// cheap enough to call for a single request.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::{ClientVersion, Docker};
+    ///
+    ///     let docker = Docker::connect_with_http_defaults().unwrap();
+    ///     let version = ClientVersion {
+    ///         major_version: 1,
+    ///         minor_version: 40,
+    ///     };
+    ///     docker.with_client_version(&version).info();
+    /// ```
+    pub fn with_client_version(&self, client_version: &ClientVersion) -> Docker {
+        Docker {
+            version: Arc::new((
+                AtomicUsize::new(client_version.major_version),
+                AtomicUsize::new(client_version.minor_version),
+            )),
+            ..self.clone()
+        }
+    }
+
+    /// Set the client version from a version string, such as `1.41`, to communicate with the
+    /// server.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_http_defaults()
+    ///         .unwrap()
+    ///         .with_version("1.41")
+    ///         .unwrap();
+    /// ```
+    pub fn with_version(self, version: &str) -> Result<Self, Error> {
+        match version.into() {
+            MaybeClientVersion::Some(client_version) => {
+                Ok(self.with_client_version(&client_version))
+            }
+            MaybeClientVersion::None => Err(APIVersionParseError {
+                api_version: version.to_string(),
+            }),
+        }
+    }
+
     /// Check with the server for a supported version, and downgrade the client version if
     /// appropriate.
     ///
@@ -1057,7 +1300,10 @@ impl Docker {
     ///     };
     /// ```
     pub async fn negotiate_version(self) -> Result<Self, Error> {
-        let req = self.build_request(
+        // The current client version may be too new for the daemon, so ask on the unversioned
+        // endpoint.
+        let req = self.build_request_with_version(
+            None,
             "/version",
             Builder::new().method(Method::GET),
             None::<String>,
@@ -1120,9 +1366,13 @@ impl Docker {
                             .map(|msg| msg.message)
                             .or_else(|e| if e.is_data() { Ok(contents) } else { Err(e) })?;
                     }
-                    Err(DockerResponseServerError {
-                        status_code: status.as_u16(),
-                        message,
+                    Err(match status {
+                        StatusCode::NOT_FOUND => DockerResponseNotFoundError { message },
+                        StatusCode::CONFLICT => DockerResponseConflictError { message },
+                        _ => DockerResponseServerError {
+                            status_code: status.as_u16(),
+                            message,
+                        },
                     })
                 }
             }
@@ -1136,6 +1386,20 @@ impl Docker {
         query: Option<O>,
         payload: Result<Body, Error>,
     ) -> Result<Request<Body>, Error>
+    where
+        O: Serialize,
+    {
+        self.build_request_with_version(Some(&self.client_version()), path, builder, query, payload)
+    }
+
+    fn build_request_with_version<O>(
+        &self,
+        client_version: Option<&ClientVersion>,
+        path: &str,
+        builder: Builder,
+        query: Option<O>,
+        payload: Result<Body, Error>,
+    ) -> Result<Request<Body>, Error>
     where
         O: Serialize,
     {
@@ -1144,10 +1408,14 @@ impl Docker {
             &self.client_type,
             path,
             query,
-            &self.client_version(),
+            client_version,
         )?;
         let request_uri: hyper::Uri = uri.into();
         debug!("{}", &request_uri);
+        let builder = match self.user_agent {
+            Some(ref user_agent) => builder.header(USER_AGENT, user_agent.as_str()),
+            None => builder,
+        };
         Ok(builder
             .uri(request_uri)
             .header(CONTENT_TYPE, "application/json")
@@ -1225,3 +1493,301 @@ impl Docker {
         })
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use http::header::USER_AGENT;
+    use http::request::Builder;
+    use hyper::{Body, Method};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use std::time::Duration;
+
+    #[cfg(feature = "ssl")]
+    use super::tls_verify_enabled;
+    use super::ClientType;
+    use crate::errors::Error;
+    use crate::{ClientVersion, Docker, API_DEFAULT_VERSION};
+
+    #[test]
+    fn test_with_client_version() {
+        let docker = Docker::connect_with_mock(
+            HostToReplyConnector::default(),
+            "127.0.0.1".to_string(),
+            5,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let version = ClientVersion {
+            major_version: 1,
+            minor_version: 38,
+        };
+        let req = docker
+            .with_client_version(&version)
+            .build_request(
+                "/info",
+                Builder::new().method(Method::GET),
+                None::<String>,
+                Ok(Body::empty()),
+            )
+            .unwrap();
+
+        assert_eq!(req.uri().path(), "/v1.38/info");
+        assert_eq!(docker.client_version(), *API_DEFAULT_VERSION);
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_version() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"ApiVersion\":\"1.30\",\"MinAPIVersion\":\"1.12\"}".to_string(),
+        );
+
+        let version = ClientVersion {
+            major_version: 1,
+            minor_version: 41,
+        };
+        let docker = Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, &version)
+            .unwrap()
+            .negotiate_version()
+            .await
+            .unwrap();
+
+        assert_eq!(docker.client_version().to_string(), "1.30");
+
+        let req = docker
+            .build_request(
+                "/info",
+                Builder::new().method(Method::GET),
+                None::<String>,
+                Ok(Body::empty()),
+            )
+            .unwrap();
+
+        assert_eq!(req.uri().path(), "/v1.30/info");
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_version_rejects_newer_version() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Mimic a daemon that only speaks API 1.30, rejecting requests for a newer version.
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0; 4096];
+                let len = stream.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..len]);
+                let path = request.split(' ').nth(1).unwrap_or_default();
+                let response = if path.starts_with("/v1.41/") {
+                    let body = "{\"message\":\"client version 1.41 is too new\"}";
+                    format!(
+                        "HTTP/1.1 400 Bad Request\r\nContent-Type:application/json\r\nContent-Length:{}\r\nConnection:close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    let body = "{\"ApiVersion\":\"1.30\",\"MinAPIVersion\":\"1.12\"}";
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type:application/json\r\nContent-Length:{}\r\nConnection:close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let docker = Docker::connect_with_http(&format!("tcp://{}", addr), 5, API_DEFAULT_VERSION)
+            .unwrap()
+            .with_version("1.41")
+            .unwrap();
+
+        let err = docker.version().await.unwrap_err();
+        assert_eq!(err.status_code(), Some(400));
+
+        let docker = docker.negotiate_version().await.unwrap();
+        assert_eq!(docker.client_version().to_string(), "1.30");
+
+        let version = docker.version().await.unwrap();
+        assert_eq!(version.api_version.as_deref(), Some("1.30"));
+    }
+
+    #[test]
+    #[cfg(feature = "ssl")]
+    fn test_tls_verify_enabled() {
+        assert!(tls_verify_enabled(Some("1")));
+        assert!(!tls_verify_enabled(Some("")));
+        assert!(!tls_verify_enabled(None));
+    }
+
+    #[test]
+    fn test_builder_scheme_routing() {
+        let docker = Docker::builder()
+            .addr("unix:///var/run/docker.sock")
+            .build()
+            .unwrap();
+        assert!(matches!(docker.client_type, ClientType::Unix));
+        assert_eq!(docker.client_addr, "/var/run/docker.sock");
+
+        let docker = Docker::builder()
+            .addr("tcp://localhost:2375")
+            .timeout(Duration::from_secs(30))
+            .client_version(&ClientVersion {
+                major_version: 1,
+                minor_version: 38,
+            })
+            .build()
+            .unwrap();
+        assert!(matches!(docker.client_type, ClientType::Http));
+        assert_eq!(docker.client_addr, "localhost:2375");
+        assert_eq!(docker.client_timeout, 30);
+        assert_eq!(docker.client_version().to_string(), "1.38");
+
+        let docker = Docker::builder()
+            .addr("tcp://localhost:2375")
+            .timeout(Duration::from_millis(500))
+            .build()
+            .unwrap();
+        assert_eq!(docker.client_timeout, 1);
+
+        let docker = Docker::builder().build().unwrap();
+        assert!(matches!(docker.client_type, ClientType::Unix));
+        assert_eq!(docker.client_addr, "/var/run/docker.sock");
+    }
+
+    #[test]
+    fn test_builder_user_agent() {
+        let docker = Docker::builder()
+            .addr("tcp://localhost:2375")
+            .user_agent("deploy-bot/1.0")
+            .build()
+            .unwrap();
+
+        let req = docker
+            .build_request(
+                "/info",
+                Builder::new().method(Method::GET),
+                None::<String>,
+                Ok(Body::empty()),
+            )
+            .unwrap();
+        assert_eq!(req.headers()[USER_AGENT], "deploy-bot/1.0");
+
+        let req = Docker::builder()
+            .addr("tcp://localhost:2375")
+            .build()
+            .unwrap()
+            .build_request(
+                "/info",
+                Builder::new().method(Method::GET),
+                None::<String>,
+                Ok(Body::empty()),
+            )
+            .unwrap();
+        assert!(req.headers().get(USER_AGENT).is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "ssl"))]
+    fn test_builder_https_without_ssl_feature() {
+        assert!(matches!(
+            Docker::builder().addr("https://localhost:2376").build(),
+            Err(Error::SslFeatureDisabledError { addr }) if addr == "https://localhost:2376"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "ssl")]
+    fn test_builder_ssl_errors() {
+        assert!(matches!(
+            Docker::builder().addr("https://localhost:2376").build(),
+            Err(Error::MissingSslCertificatesError { addr }) if addr == "https://localhost:2376"
+        ));
+
+        let certs = std::path::Path::new("/certs");
+        assert!(matches!(
+            Docker::builder()
+                .ssl(
+                    &certs.join("key.pem"),
+                    &certs.join("cert.pem"),
+                    &certs.join("ca.pem")
+                )
+                .build(),
+            Err(Error::MissingSslAddressError)
+        ));
+    }
+
+    #[test]
+    fn test_with_version() {
+        let docker = Docker::connect_with_mock(
+            HostToReplyConnector::default(),
+            "127.0.0.1".to_string(),
+            5,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let docker = docker.with_version("1.41").unwrap();
+        assert_eq!(docker.client_version().to_string(), "1.41");
+
+        assert!(matches!(
+            docker.with_version("latest"),
+            Err(Error::APIVersionParseError { api_version }) if api_version == "latest"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_error_status_variants() {
+        for (status, message) in [
+            ("404 Not Found", "no such container: web"),
+            ("409 Conflict", "container name web is already in use"),
+            ("500 Internal Server Error", "boom"),
+        ] {
+            let mut connector = HostToReplyConnector::default();
+            connector.m.insert(
+                String::from("http://127.0.0.1"),
+                format!(
+                    "HTTP/1.1 {}\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{{\"message\":\"{}\"}}",
+                    status, message
+                ),
+            );
+            let docker = Docker::connect_with_mock(
+                connector,
+                "127.0.0.1".to_string(),
+                5,
+                API_DEFAULT_VERSION,
+            )
+            .unwrap();
+
+            let err = docker.version().await.unwrap_err();
+            match status {
+                "404 Not Found" => assert!(matches!(
+                    err,
+                    Error::DockerResponseNotFoundError { ref message } if message == "no such container: web"
+                )),
+                "409 Conflict" => assert!(matches!(err, Error::DockerResponseConflictError { .. })),
+                _ => assert!(matches!(
+                    err,
+                    Error::DockerResponseServerError {
+                        status_code: 500,
+                        ..
+                    }
+                )),
+            }
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "Docker responded with status code {}: {}",
+                    &status[..3],
+                    message
+                )
+            );
+        }
+    }
+}
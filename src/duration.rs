@@ -0,0 +1,78 @@
+//! Conversions between [`Duration`](std::time::Duration) and the integer nanoseconds used by
+//! duration fields in the Docker API.
+//!
+//! The generated models already expose the documented nanosecond fields, such as the intervals
+//! of a [`HealthConfig`](crate::models::HealthConfig), as a `Duration`:
+//!
+//! ```rust
+//! use bollard::models::HealthConfig;
+//!
+//! use std::time::Duration;
+//!
+//! HealthConfig {
+//!     interval: Some(Duration::from_secs(5)),
+//!     timeout: Some(Duration::from_millis(500)),
+//!     ..Default::default()
+//! };
+//! ```
+//!
+//! The module itself can be used with `#[serde(with = "bollard::duration")]` on a `Duration`
+//! field, and [`option`] on an `Option<Duration>` field, of your own structs:
+//!
+//! ```rust
+//! # #[macro_use] extern crate serde_derive;
+//! use std::time::Duration;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! #[serde(rename_all = "PascalCase")]
+//! struct Probe {
+//!     #[serde(with = "bollard::duration")]
+//!     interval: Duration,
+//!     #[serde(with = "bollard::duration::option")]
+//!     start_period: Option<Duration>,
+//! }
+//! # fn main() {}
+//! ```
+
+pub use bollard_stubs::duration::{deserialize, from_nanos, option, serialize, to_nanos};
+
+#[cfg(test)]
+mod tests {
+    use super::{from_nanos, to_nanos};
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_nanos_round_trip() {
+        assert_eq!(to_nanos(Duration::from_secs(5)), 5_000_000_000);
+        assert_eq!(from_nanos(5_000_000_000), Some(Duration::from_secs(5)));
+        assert_eq!(to_nanos(Duration::MAX), i64::MAX);
+        assert_eq!(from_nanos(-1), None);
+
+        let json = serde_json::json!({ "Interval": 30_000_000_000i64, "StartPeriod": null });
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Probe {
+            #[serde(with = "super")]
+            interval: Duration,
+            #[serde(with = "super::option")]
+            start_period: Option<Duration>,
+        }
+
+        let probe: Probe = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            probe,
+            Probe {
+                interval: Duration::from_secs(30),
+                start_period: None,
+            }
+        );
+        assert_eq!(serde_json::to_value(&probe).unwrap(), json);
+
+        let json = serde_json::json!({ "Interval": -1, "StartPeriod": -1 });
+        assert!(serde_json::from_value::<Probe>(json).is_err());
+        let json = serde_json::json!({ "Interval": 0, "StartPeriod": -1 });
+        assert!(serde_json::from_value::<Probe>(json).is_err());
+    }
+}
@@ -34,6 +34,27 @@ pub enum Error {
         /// Path for the failing certificate file
         path: PathBuf,
     },
+    /// Error emitted when connecting with SSL defaults without `DOCKER_TLS_VERIFY` set
+    #[cfg(feature = "ssl")]
+    #[error("DOCKER_TLS_VERIFY must be set to connect with the SSL defaults")]
+    SslVerifyDisabledError,
+    /// Error emitted when connecting over SSL without configuring any certificates
+    #[cfg(feature = "ssl")]
+    #[error("SSL certificates are required to connect to: {addr}")]
+    MissingSslCertificatesError {
+        /// Address of the daemon
+        addr: String,
+    },
+    /// Error emitted when SSL certificates are configured without the address to connect to
+    #[cfg(feature = "ssl")]
+    #[error("An address is required to connect over SSL")]
+    MissingSslAddressError,
+    /// Error emitted when connecting to an `https://` address without the `ssl` feature
+    #[error("The ssl feature is required to connect to: {addr}")]
+    SslFeatureDisabledError {
+        /// Address of the daemon
+        addr: String,
+    },
     /// Error emitted when the client is unable to load native certs for SSL
     #[cfg(feature = "ssl")]
     #[error("Could not load native certs")]
@@ -41,6 +62,25 @@ pub enum Error {
         /// The original error emitted.
         err: webpki::Error,
     },
+    /// Error emitted when the options of a request are rejected before reaching the server
+    #[error("Invalid request: {message}")]
+    ValidationError {
+        /// Description of the invalid option combination
+        message: String,
+    },
+    /// Error emitted by the docker server when the requested object does not exist.
+    #[error("Docker responded with status code 404: {message}")]
+    DockerResponseNotFoundError {
+        /// Message returned by the docker server.
+        message: String,
+    },
+    /// Error emitted by the docker server when the request conflicts with the state of an
+    /// object, e.g. because an object with the same name already exists or is in use.
+    #[error("Docker responded with status code 409: {message}")]
+    DockerResponseConflictError {
+        /// Message returned by the docker server.
+        message: String,
+    },
     /// Generic error emitted by the docker server.
     #[error("Docker responded with status code {status_code}: {message}")]
     DockerResponseServerError {
@@ -69,12 +109,54 @@ pub enum Error {
     /// Error emitted when a request times out.
     #[error("Timeout error")]
     RequestTimeoutError,
+    /// Error emitted when a container port does not accept connections within the time waited for it
+    #[error("Port {port} of container {container} was not ready in time")]
+    PortWaitTimeoutError {
+        /// Name of the container
+        container: String,
+        /// Container port waited for
+        port: u16,
+    },
+    /// Error emitted when an exec instance does not report an exit code after its output ended
+    #[error("Exec {id} did not report an exit code")]
+    ExecExitCodeError {
+        /// ID of the exec instance
+        id: String,
+    },
     /// Error emitted mid-stream as part of a successful docker operation
     #[error("Docker stream error")]
     DockerStreamError {
         /// error string emitted by the Stream
         error: String,
     },
+    /// Error emitted when the content of an exported image does not match its digests
+    #[error("Digest mismatch for {path} in image archive: expected {expected}, found {actual}")]
+    ImageDigestMismatchError {
+        /// Path of the mismatched file in the archive
+        path: String,
+        /// Digest recorded by the daemon for the image
+        expected: String,
+        /// Digest of the exported content
+        actual: String,
+    },
+    /// Error emitted when an exported image archive ends in the middle of an entry
+    #[error("Image archive ended before the end of {path}")]
+    ImageArchiveTruncatedError {
+        /// Path of the incomplete entry, empty if the archive ended inside a header
+        path: String,
+    },
+    /// Error emitted when a BuildKit trace in a build stream cannot be decoded
+    #[error("Failed to decode BuildKit trace: {message}")]
+    BuildKitTraceError {
+        /// Description of the malformed trace
+        message: String,
+    },
+    /// Error emitted when the path stat header of a container archive cannot be decoded
+    #[error("Failed to decode container path stat: {message}")]
+    ContainerPathStatError {
+        /// Description of the malformed header
+        message: String,
+    },
     /// Error emitted as part of a container wait response
     #[error("Docker container wait error")]
     DockerContainerWaitError {
@@ -133,3 +215,40 @@ pub enum Error {
         err: serde_urlencoded::ser::Error,
     },
 }
+
+impl Error {
+    /// The HTTP status code of an error response from the docker server, if this is one.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Error::DockerResponseNotFoundError { .. } => Some(404),
+            Error::DockerResponseConflictError { .. } => Some(409),
+            Error::DockerResponseServerError { status_code, .. } => Some(*status_code),
+            _ => None,
+        }
+    }
+
+    /// Whether the docker server responded with `404 Not Found`, e.g. because the container,
+    /// image or network does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bollard::errors::Error;
+    ///
+    /// let err = Error::DockerResponseNotFoundError {
+    ///     message: String::from("network web not found"),
+    /// };
+    ///
+    /// assert!(err.is_not_found());
+    /// assert!(!err.is_conflict());
+    /// ```
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::DockerResponseNotFoundError { .. })
+    }
+
+    /// Whether the docker server responded with `409 Conflict`, e.g. because an object with the
+    /// same name already exists or is in use.
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Error::DockerResponseConflictError { .. })
+    }
+}
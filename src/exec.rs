@@ -13,8 +13,10 @@ use crate::errors::Error;
 use crate::models::ExecInspectResponse;
 use crate::read::NewlineLogOutputDecoder;
 use futures_core::Stream;
+use futures_util::StreamExt;
 use std::fmt::{Debug, Formatter};
 use std::pin::Pin;
+use std::time::Duration;
 use tokio::io::AsyncWrite;
 use tokio_util::codec::FramedRead;
 
@@ -68,12 +70,16 @@ pub struct StartExecOptions {
 }
 
 /// Result type for the [Start Exec API](Docker::start_exec())
-#[allow(missing_docs)]
 pub enum StartExecResults {
+    /// The exec was started attached, over a connection upgraded for bidirectional I/O.
     Attached {
+        /// Output of the command, if `attach_stdout` or `attach_stderr` were set.
         output: Pin<Box<dyn Stream<Item = Result<LogOutput, Error>> + Send>>,
+        /// Standard input of the command, if `attach_stdin` was set. Shutting the writer down
+        /// closes the command's standard input.
         input: Pin<Box<dyn AsyncWrite + Send>>,
     },
+    /// The exec was started with `detach` set.
     Detached,
 }
 
@@ -86,6 +92,17 @@ impl Debug for StartExecResults {
     }
 }
 
+/// Result type for the [Exec Simple API](Docker::exec_simple())
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecOutput {
+    /// Everything the command wrote to `stdout`, or to the TTY.
+    pub stdout: String,
+    /// Everything the command wrote to `stderr`.
+    pub stderr: String,
+    /// Exit code of the command.
+    pub exit_code: i64,
+}
+
 /// Resize configuration used in the [Resize Exec API](Docker::resize_exec())
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -113,7 +130,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Create Exec Results](CreateExecResults) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -242,6 +259,88 @@ impl Docker {
         }
     }
 
+    /// ---
+    ///
+    /// # Exec Simple
+    ///
+    /// Run a command inside a running container and wait for it to finish, collecting its output.
+    /// This creates, starts and inspects an exec instance in one call. Once the output ends, the
+    /// exec is inspected until it stops running, for up to 5 seconds, and an [Exec Exit Code
+    /// Error](Error::ExecExitCodeError) is returned if it has not reported an exit code by then.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - The command to run, as a vector of string slices.
+    ///
+    /// # Returns
+    ///
+    ///  - An [Exec Output](ExecOutput) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.exec_simple("hello-world", vec!["ps", "-ef"]);
+    /// ```
+    pub async fn exec_simple(
+        &self,
+        container_name: &str,
+        cmd: Vec<&str>,
+    ) -> Result<ExecOutput, Error> {
+        let exec = self
+            .create_exec(
+                container_name,
+                CreateExecOptions {
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    cmd: Some(cmd),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        if let StartExecResults::Attached { mut output, .. } =
+            self.start_exec(&exec.id, None).await?
+        {
+            while let Some(log) = output.next().await {
+                match log? {
+                    LogOutput::StdErr { message } => stderr.extend_from_slice(&message),
+                    LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                        stdout.extend_from_slice(&message)
+                    }
+                    LogOutput::StdIn { .. } => (),
+                }
+            }
+        }
+
+        // The exec can still be reported as running for a moment after its output ends.
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+        const POLL_ATTEMPTS: usize = 100;
+
+        let mut attempts = 1;
+        let inspect = loop {
+            let inspect = self.inspect_exec(&exec.id).await?;
+            if inspect.running == Some(false) || attempts == POLL_ATTEMPTS {
+                break inspect;
+            }
+            attempts += 1;
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        match (inspect.running, inspect.exit_code) {
+            (Some(false), Some(exit_code)) => Ok(ExecOutput {
+                stdout: String::from_utf8_lossy(&stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&stderr).into_owned(),
+                exit_code,
+            }),
+            _ => Err(Error::ExecExitCodeError { id: exec.id }),
+        }
+    }
+
     /// ---
     ///
     /// # Inspect Exec
@@ -340,3 +439,71 @@ impl Docker {
         self.process_into_unit(req).await
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use super::{CreateExecOptions, StartExecOptions, StartExecResults};
+
+    #[tokio::test]
+    async fn test_create_exec() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 201 Created\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"f90e34656806\"}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let options = CreateExecOptions {
+            attach_stdout: Some(true),
+            cmd: Some(vec!["ls", "-l"]),
+            env: Some(vec!["DEBUG=1"]),
+            user: Some("nobody"),
+            working_dir: Some("/tmp"),
+            ..Default::default()
+        };
+
+        let body = serde_json::to_value(&options).unwrap();
+        assert_eq!(body["AttachStdout"], true);
+        assert_eq!(body["Cmd"], serde_json::json!(["ls", "-l"]));
+        assert_eq!(body["Env"], serde_json::json!(["DEBUG=1"]));
+        assert_eq!(body["User"], "nobody");
+        assert_eq!(body["WorkingDir"], "/tmp");
+
+        let result = docker.create_exec("exec_test", options).await.unwrap();
+
+        assert_eq!(result.id, "f90e34656806");
+    }
+
+    #[tokio::test]
+    async fn test_start_exec_detached() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Length:0\r\n\r\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .start_exec(
+                "exec_test",
+                Some(StartExecOptions {
+                    detach: true,
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(result, StartExecResults::Detached));
+    }
+}
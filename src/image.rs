@@ -1,20 +1,32 @@
 //! Image API: creating, manipulating and pushing docker images
 use futures_core::Stream;
-use futures_util::{stream, stream::StreamExt};
+use futures_util::{
+    future, stream,
+    stream::{StreamExt, TryStreamExt},
+};
 use http::header::CONTENT_TYPE;
 use http::request::Builder;
 use hyper::{body::Bytes, Body, Method};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::Docker;
 use crate::auth::{base64_url_encode, DockerCredentials};
 use crate::container::Config;
 use crate::errors::Error;
+use crate::image_archive::{read_archive, ChunkReader};
 use crate::models::*;
 
 use std::cmp::Eq;
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
 use std::hash::Hash;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
 
 /// Parameters available for pulling an image, used in the [Create Image
 /// API](Docker::create_image)
@@ -142,13 +154,54 @@ where
 {
     /// Filters to process on the prune list, encoded as JSON. Available filters:
     ///  - `dangling=<boolean>` When set to `true` (or `1`), prune only unused *and* untagged
-    ///  images. When set to `false` (or `0`), all unused images are pruned.
+    ///    images. When set to `false` (or `0`), all unused images are pruned.
     ///  - `until=<string>` Prune images created before this timestamp. The `<timestamp>` can be
-    ///  Unix timestamps, date formatted timestamps, or Go duration strings (e.g. `10m`, `1h30m`)
-    ///  computed relative to the daemon machine’s time.
+    ///    Unix timestamps, date formatted timestamps, or Go duration strings (e.g. `10m`, `1h30m`)
+    ///    computed relative to the daemon machine’s time.
     ///  - `label` (`label=<key>`, `label=<key>=<value>`, `label!=<key>`, or
-    ///  `label!=<key>=<value>`) Prune images with (or without, in case `label!=...` is used) the
-    ///  specified labels.
+    ///    `label!=<key>=<value>`) Prune images with (or without, in case `label!=...` is used) the
+    ///    specified labels.
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Parameters to the [Prune Build API](Docker::prune_build())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::image::PruneBuildOptions;
+///
+/// use std::collections::HashMap;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("until", vec!["24h"]);
+///
+/// PruneBuildOptions{
+///   all: true,
+///   filters,
+///   ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PruneBuildOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Remove all unused build cache, not just dangling build cache.
+    pub all: bool,
+    /// Amount of disk space in bytes to keep for the build cache.
+    #[serde(rename = "keep-storage", skip_serializing_if = "Option::is_none")]
+    pub keep_storage: Option<i64>,
+    /// Filters to process on the prune list, encoded as JSON. Available filters:
+    ///  - `until=<duration>` Remove cache older than the duration, e.g. `24h`.
+    ///  - `id=<id>`
+    ///  - `parent=<id>`
+    ///  - `type=<string>`
+    ///  - `description=<string>`
+    ///  - `inuse`
+    ///  - `shared`
+    ///  - `private`
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
 }
@@ -286,7 +339,7 @@ where
 ///
 /// CommitContainerOptions {
 ///     container: "my-running-container",
-///     pause: true,
+///     pause: false,
 ///     ..Default::default()
 /// };
 /// ```
@@ -298,7 +351,7 @@ where
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CommitContainerOptions<T>
 where
     T: Into<String> + Serialize,
@@ -313,12 +366,31 @@ where
     pub comment: T,
     /// Author of the image.
     pub author: T,
-    /// Whether to pause the container before committing.
+    /// Whether to pause the container before committing. Defaults to `true`, as in the Docker
+    /// daemon. Committing without pausing avoids stalling the container, at the cost of a possibly
+    /// inconsistent snapshot of its filesystem.
     pub pause: bool,
     /// `Dockerfile` instructions to apply while committing
     pub changes: Option<T>,
 }
 
+impl<T> Default for CommitContainerOptions<T>
+where
+    T: Into<String> + Serialize + Default,
+{
+    fn default() -> Self {
+        CommitContainerOptions {
+            container: T::default(),
+            repo: T::default(),
+            tag: T::default(),
+            comment: T::default(),
+            author: T::default(),
+            pause: true,
+            changes: None,
+        }
+    }
+}
+
 /// Parameters to the [Build Image API](Docker::build_image())
 ///
 /// ## Examples
@@ -405,6 +477,185 @@ where
     pub platform: T,
 }
 
+/// Builder for the tar archive used as the build context of the [Build Image
+/// API](Docker::build_image()), honoring `.dockerignore` rules.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use bollard::image::BuildContext;
+///
+/// let mut context = BuildContext::new();
+/// context
+///     .add_file("Dockerfile", b"FROM alpine\nCOPY . /app\n")
+///     .unwrap()
+///     .add_dir("./app")
+///     .unwrap();
+/// let body = context.finish().unwrap();
+/// ```
+pub struct BuildContext {
+    archive: tar::Builder<Vec<u8>>,
+    ignore: Vec<IgnorePattern>,
+}
+
+struct IgnorePattern {
+    negate: bool,
+    components: Vec<String>,
+}
+
+impl Default for BuildContext {
+    fn default() -> Self {
+        BuildContext::new()
+    }
+}
+
+impl fmt::Debug for BuildContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BuildContext")
+    }
+}
+
+impl BuildContext {
+    /// Create an empty build context.
+    pub fn new() -> BuildContext {
+        BuildContext {
+            archive: tar::Builder::new(Vec::new()),
+            ignore: Vec::new(),
+        }
+    }
+
+    /// Exclude files added afterwards that match the rules of a `.dockerignore` file.
+    pub fn dockerignore(&mut self, contents: &str) -> &mut Self {
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negate, pattern) = match line.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, line),
+            };
+            self.ignore.push(IgnorePattern {
+                negate,
+                components: path_components(pattern).map(String::from).collect(),
+            });
+        }
+        self
+    }
+
+    /// Add a file with the given contents at a path in the build context, unless it is excluded.
+    pub fn add_file(&mut self, path: &str, contents: &[u8]) -> Result<&mut Self, Error> {
+        if !self.is_ignored(path) {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            self.archive.append_data(&mut header, path, contents)?;
+        }
+        Ok(self)
+    }
+
+    /// Add the contents of a directory at the root of the build context. A `.dockerignore` file
+    /// at the root of the directory is applied to the files below it.
+    pub fn add_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<&mut Self, Error> {
+        let dir = dir.as_ref();
+        if let Ok(contents) = fs::read_to_string(dir.join(".dockerignore")) {
+            self.dockerignore(&contents);
+        }
+        self.add_dir_entries(dir, "")?;
+        Ok(self)
+    }
+
+    fn add_dir_entries(&mut self, dir: &Path, prefix: &str) -> Result<(), Error> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            let ignored = self.is_ignored(&name);
+
+            if entry.file_type()?.is_dir() {
+                if !ignored {
+                    self.archive.append_dir(&name, entry.path())?;
+                }
+                // An excluded directory is only walked if a negated pattern can re-include files
+                // below it, so that large ignored trees such as `target` are skipped.
+                if !ignored || self.may_include_below(&name) {
+                    self.add_dir_entries(&entry.path(), &format!("{}/", name))?;
+                }
+            } else if !ignored {
+                self.archive.append_path_with_name(entry.path(), &name)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_ignored(&self, path: &str) -> bool {
+        let path: Vec<&str> = path_components(path).collect();
+
+        // A pattern matching a parent directory excludes everything below it, the last matching
+        // pattern wins.
+        self.ignore.iter().fold(false, |ignored, pattern| {
+            if (1..=path.len()).any(|len| matches_components(&pattern.components, &path[..len])) {
+                !pattern.negate
+            } else {
+                ignored
+            }
+        })
+    }
+
+    fn may_include_below(&self, dir: &str) -> bool {
+        let dir: Vec<&str> = path_components(dir).collect();
+        self.ignore
+            .iter()
+            .any(|pattern| pattern.negate && matches_below(&pattern.components, &dir))
+    }
+
+    /// Finish the archive, returning a body suitable for the [Build Image
+    /// API](Docker::build_image()).
+    pub fn finish(self) -> Result<Body, Error> {
+        Ok(Body::from(self.archive.into_inner()?))
+    }
+}
+
+fn path_components(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+}
+
+fn matches_components(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| matches_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path)) => {
+                matches_component(first.as_bytes(), name.as_bytes())
+                    && matches_components(rest, path)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Whether the pattern can match a path strictly below the directory.
+fn matches_below(pattern: &[String], dir: &[&str]) -> bool {
+    match (pattern.split_first(), dir.split_first()) {
+        (Some((first, _)), _) if first == "**" => true,
+        (Some(_), None) => true,
+        (Some((first, rest)), Some((name, dir))) => {
+            matches_component(first.as_bytes(), name.as_bytes()) && matches_below(rest, dir)
+        }
+        (None, _) => false,
+    }
+}
+
+fn matches_component(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| matches_component(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && matches_component(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && matches_component(rest, &name[1..]),
+    }
+}
+
 /// Parameters to the [Import Image API](Docker::import_image())
 ///
 /// ## Examples
@@ -424,6 +675,261 @@ pub struct ImportImageOptions {
     pub quiet: bool,
 }
 
+/// Progress of a step in a BuildKit build, as returned by the [Build Image Vertices
+/// API](Docker::build_image_vertices())
+///
+/// BuildKit reports a vertex again every time its status changes, so consumers rendering the build
+/// graph should key vertices by their `digest`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildKitVertex {
+    /// Digest identifying the vertex in the build graph.
+    pub digest: String,
+    /// Digests of the vertices this vertex depends on.
+    pub inputs: Vec<String>,
+    /// Human readable description of the step, e.g. `[2/3] RUN make`.
+    pub name: String,
+    /// Time at which the step started, if it has.
+    pub started: Option<SystemTime>,
+    /// Time at which the step completed, if it has.
+    pub completed: Option<SystemTime>,
+    /// Whether the result of the step was taken from the build cache.
+    pub cached: bool,
+    /// Error message, if the step failed.
+    pub error: Option<String>,
+}
+
+/// Build stream message, keeping the `aux` payload undecoded so BuildKit traces can be read.
+#[derive(Debug, Deserialize)]
+struct BuildKitMessage {
+    id: Option<String>,
+    aux: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+const BUILDKIT_TRACE_ID: &str = "moby.buildkit.trace";
+
+/// Minimal protobuf reader, covering the wire types used by the BuildKit `StatusResponse` message.
+struct ProtoReader<'a> {
+    buf: &'a [u8],
+}
+
+enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        ProtoReader { buf }
+    }
+
+    fn truncated() -> Error {
+        Error::BuildKitTraceError {
+            message: String::from("unexpected end of message"),
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.buf.len() < len {
+            return Err(Self::truncated());
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::BuildKitTraceError {
+            message: String::from("varint is too long"),
+        })
+    }
+
+    fn field(&mut self) -> Result<Option<(u64, ProtoValue<'a>)>, Error> {
+        if self.buf.is_empty() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let value = match key & 0x7 {
+            0 => ProtoValue::Varint(self.varint()?),
+            1 => {
+                self.take(8)?;
+                ProtoValue::Fixed
+            }
+            2 => {
+                let len = self.varint()? as usize;
+                ProtoValue::Bytes(self.take(len)?)
+            }
+            5 => {
+                self.take(4)?;
+                ProtoValue::Fixed
+            }
+            wire_type => {
+                return Err(Error::BuildKitTraceError {
+                    message: format!("unsupported wire type {}", wire_type),
+                })
+            }
+        };
+        Ok(Some((key >> 3, value)))
+    }
+}
+
+fn proto_string(bytes: &[u8]) -> Result<String, Error> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| Error::BuildKitTraceError {
+        message: e.to_string(),
+    })
+}
+
+/// Decode a `google.protobuf.Timestamp`.
+fn decode_proto_timestamp(bytes: &[u8]) -> Result<Option<SystemTime>, Error> {
+    let mut reader = ProtoReader::new(bytes);
+    let (mut seconds, mut nanos) = (0i64, 0i32);
+    while let Some((field, value)) = reader.field()? {
+        match (field, value) {
+            (1, ProtoValue::Varint(value)) => seconds = value as i64,
+            (2, ProtoValue::Varint(value)) => nanos = value as i32,
+            _ => {}
+        }
+    }
+    // BuildKit sends the zero timestamp for steps that have not started or completed yet.
+    if seconds <= 0 && nanos <= 0 {
+        return Ok(None);
+    }
+    Ok(Some(
+        SystemTime::UNIX_EPOCH + Duration::new(seconds.max(0) as u64, nanos.max(0) as u32),
+    ))
+}
+
+/// Decode a BuildKit `Vertex` message.
+fn decode_buildkit_vertex(bytes: &[u8]) -> Result<BuildKitVertex, Error> {
+    let mut reader = ProtoReader::new(bytes);
+    let mut vertex = BuildKitVertex::default();
+    while let Some((field, value)) = reader.field()? {
+        match (field, value) {
+            (1, ProtoValue::Bytes(bytes)) => vertex.digest = proto_string(bytes)?,
+            (2, ProtoValue::Bytes(bytes)) => vertex.inputs.push(proto_string(bytes)?),
+            (3, ProtoValue::Bytes(bytes)) => vertex.name = proto_string(bytes)?,
+            (4, ProtoValue::Varint(value)) => vertex.cached = value != 0,
+            (5, ProtoValue::Bytes(bytes)) => vertex.started = decode_proto_timestamp(bytes)?,
+            (6, ProtoValue::Bytes(bytes)) => vertex.completed = decode_proto_timestamp(bytes)?,
+            (7, ProtoValue::Bytes(bytes)) if !bytes.is_empty() => {
+                vertex.error = Some(proto_string(bytes)?)
+            }
+            _ => {}
+        }
+    }
+    Ok(vertex)
+}
+
+/// Decode the vertices of a base64 encoded BuildKit `StatusResponse` message, ignoring its status,
+/// log and warning entries.
+fn decode_buildkit_trace(aux: &str) -> Result<Vec<BuildKitVertex>, Error> {
+    let bytes = base64::decode(aux).map_err(|e| Error::BuildKitTraceError {
+        message: e.to_string(),
+    })?;
+    let mut reader = ProtoReader::new(&bytes);
+    let mut vertices = Vec::new();
+    while let Some((field, value)) = reader.field()? {
+        if let (1, ProtoValue::Bytes(bytes)) = (field, value) {
+            vertices.push(decode_buildkit_vertex(bytes)?);
+        }
+    }
+    Ok(vertices)
+}
+
+/// A layer of an image, as returned by the [Image Layers API](Docker::image_layers())
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageLayer {
+    /// Digest of the uncompressed layer, from the image's root filesystem.
+    pub digest: String,
+    /// Size of the layer in bytes, if a history entry could be matched to the layer.
+    pub size: Option<i64>,
+    /// Instruction that created the layer, if a history entry could be matched to the layer.
+    pub created_by: Option<String>,
+}
+
+/// Whether a history entry created a filesystem layer, rather than only changing metadata.
+fn creates_layer(item: &HistoryResponseItem) -> bool {
+    const METADATA_INSTRUCTIONS: &[&str] = &[
+        "ARG",
+        "CMD",
+        "ENTRYPOINT",
+        "ENV",
+        "EXPOSE",
+        "HEALTHCHECK",
+        "LABEL",
+        "MAINTAINER",
+        "ONBUILD",
+        "SHELL",
+        "STOPSIGNAL",
+        "USER",
+        "VOLUME",
+        "WORKDIR",
+    ];
+
+    if item.size > 0 {
+        return true;
+    }
+    // The classic builder records metadata as `/bin/sh -c #(nop) ENV ...`, BuildKit as `ENV ...`.
+    let instruction = match item.created_by.split_once("#(nop)") {
+        Some((_, instruction)) => instruction,
+        None => &item.created_by,
+    };
+    let keyword = instruction.split_whitespace().next().unwrap_or_default();
+    !METADATA_INSTRUCTIONS.contains(&keyword)
+}
+
+/// Match root filesystem layers, oldest first, to the history entries, newest first, that created
+/// them.
+fn correlate_layers(layers: Vec<String>, history: Vec<HistoryResponseItem>) -> Vec<ImageLayer> {
+    let sized: Vec<&HistoryResponseItem> = history.iter().rev().filter(|h| h.size > 0).collect();
+    let entries = if sized.len() == layers.len() {
+        sized
+    } else {
+        history.iter().rev().filter(|h| creates_layer(h)).collect()
+    };
+
+    // If the counts still differ, the history of the base image is usually what is missing, so
+    // match the newest layers and leave the oldest unmatched.
+    let unmatched = layers.len().saturating_sub(entries.len());
+    let entries = &entries[entries.len().saturating_sub(layers.len())..];
+
+    layers
+        .into_iter()
+        .enumerate()
+        .map(|(index, digest)| {
+            let entry = index.checked_sub(unmatched).map(|index| entries[index]);
+            ImageLayer {
+                digest,
+                size: entry.map(|entry| entry.size),
+                created_by: entry.map(|entry| entry.created_by.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Split the tag off an image reference such as `localhost:5000/app:1.2.3`, ignoring the port of a
+/// registry host and references pinned by digest.
+fn split_tag(image_name: &str) -> Option<(&str, &str)> {
+    if image_name.contains('@') {
+        return None;
+    }
+    let (repository, tag) = image_name.rsplit_once(':')?;
+    if tag.contains('/') {
+        None
+    } else {
+        Some((repository, tag))
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -495,7 +1001,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -516,10 +1022,8 @@ impl Docker {
     /// // do some other work while the image is pulled from the docker hub...
     /// ```
     ///
-    /// # Unsupported
-    ///
-    ///  - Import from tarball
-    ///
+    /// To import a root file system with `Dockerfile` instructions applied, see the [Import Image
+    /// From Tar API](Docker::import_image_from_tar()).
     pub fn create_image<T>(
         &self,
         options: Option<CreateImageOptions<T>>,
@@ -562,6 +1066,83 @@ impl Docker {
         })
     }
 
+    /// ---
+    ///
+    /// # Import Image From Tar
+    ///
+    /// Create an image from a tar or tar.gz archive of a root file system, sent as the request
+    /// body, rather than pulling it from a registry.
+    ///
+    /// # Arguments
+    ///
+    ///  - Request body consisting of a tar or tar.gz archive with the root file system.
+    ///  - Repository name given to the imported image as a string slice.
+    ///  - Tag given to the imported image as a string slice.
+    ///  - `Dockerfile` instructions to apply to the image, for example `CMD ["/bin/sh"]`.
+    ///
+    /// # Returns
+    ///
+    ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous Stream. The `status` of
+    ///    the last item is the ID of the imported image.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// # let rootfs = Vec::new();
+    /// async move {
+    ///     let progress = docker
+    ///         .import_image_from_tar(rootfs.into(), "base", "latest", vec!["CMD [\"/bin/sh\"]"])
+    ///         .try_collect::<Vec<_>>()
+    ///         .await
+    ///         .unwrap();
+    ///     let id = progress.last().and_then(|info| info.status.as_ref());
+    /// };
+    /// ```
+    pub fn import_image_from_tar(
+        &self,
+        root_fs: Body,
+        repo: &str,
+        tag: &str,
+        changes: Vec<&str>,
+    ) -> impl Stream<Item = Result<CreateImageInfo, Error>> {
+        let url = "/images/create";
+
+        // The `changes` parameter is repeated once per instruction, which the options structs
+        // cannot express, so the query is serialized from pairs.
+        let mut query = vec![
+            ("fromSrc", String::from("-")),
+            ("repo", String::from(repo)),
+            ("tag", String::from(tag)),
+        ];
+        query.extend(
+            changes
+                .into_iter()
+                .map(|change| ("changes", String::from(change))),
+        );
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            Some(query),
+            Ok(root_fs),
+        );
+
+        self.process_into_stream(req).map(|res| {
+            if let Ok(CreateImageInfo {
+                error: Some(error), ..
+            }) = res
+            {
+                Err(Error::DockerStreamError { error })
+            } else {
+                res
+            }
+        })
+    }
+
     /// ---
     ///
     /// # Inspect Image
@@ -601,112 +1182,334 @@ impl Docker {
 
     /// ---
     ///
-    /// # Prune Images
+    /// # Image Exists
     ///
-    /// Delete unused images.
+    /// Check whether an image is present locally.
     ///
     /// # Arguments
     ///
-    /// - An optional [Prune Images Options](PruneImagesOptions) struct.
+    /// - Image name as a string slice.
     ///
     /// # Returns
     ///
-    ///  - a [Prune Image Response](ImagePruneResponse), wrapped in a Future.
+    ///  - A bool, wrapped in a Future.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use bollard::Docker;
     /// # let docker = Docker::connect_with_http_defaults().unwrap();
-    /// use bollard::image::PruneImagesOptions;
-    ///
-    /// use std::collections::HashMap;
-    ///
-    /// let mut filters = HashMap::new();
-    /// filters.insert("until", vec!["10m"]);
-    ///
-    /// let options = Some(PruneImagesOptions {
-    ///   filters
-    /// });
-    ///
-    /// docker.prune_images(options);
+    /// docker.image_exists("hello-world");
     /// ```
-    pub async fn prune_images<T>(
-        &self,
-        options: Option<PruneImagesOptions<T>>,
-    ) -> Result<ImagePruneResponse, Error>
-    where
-        T: Into<String> + Eq + Hash + Serialize,
-    {
-        let url = "/images/prune";
-
-        let req = self.build_request(
-            url,
-            Builder::new().method(Method::POST),
-            options,
-            Ok(Body::empty()),
-        );
-
-        self.process_into_value(req).await
+    pub async fn image_exists(&self, image_name: &str) -> Result<bool, Error> {
+        match self.inspect_image(image_name).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
     }
 
     /// ---
     ///
-    /// # Image History
+    /// # Pull If Absent
     ///
-    /// Return parent layers of an image.
+    /// Pull an image only if it is not already present locally. Without a tag in the image name,
+    /// the `latest` tag is pulled.
     ///
     /// # Arguments
     ///
     ///  - Image name as a string slice.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
     ///
     /// # Returns
     ///
-    ///  - Vector of [History Response Item](HistoryResponseItem), wrapped in a
-    ///  Future.
+    ///  - `None` if the image is already present, otherwise the progress of the pull as a
+    ///    [Create Image Info](CreateImageInfo), wrapped in a Stream. Wrapped in a Future.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use bollard::Docker;
     /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use futures_util::stream::TryStreamExt;
     ///
-    /// docker.image_history("hello-world");
+    /// async {
+    ///     if let Some(progress) = docker.pull_if_absent("hello-world", None).await.unwrap() {
+    ///         progress.try_collect::<Vec<_>>().await.unwrap();
+    ///     }
+    /// };
     /// ```
-    pub async fn image_history(&self, image_name: &str) -> Result<Vec<HistoryResponseItem>, Error> {
-        let url = format!("/images/{}/history", image_name);
-
-        let req = self.build_request(
-            &url,
-            Builder::new().method(Method::GET),
-            None::<String>,
-            Ok(Body::empty()),
-        );
+    pub async fn pull_if_absent(
+        &self,
+        image_name: &str,
+        credentials: Option<DockerCredentials>,
+    ) -> Result<Option<impl Stream<Item = Result<CreateImageInfo, Error>>>, Error> {
+        if self.image_exists(image_name).await? {
+            return Ok(None);
+        }
 
-        self.process_into_value(req).await
+        let (from_image, tag) = match split_tag(image_name) {
+            Some((repository, tag)) => (repository, tag),
+            None if image_name.contains('@') => (image_name, ""),
+            None => (image_name, "latest"),
+        };
+
+        Ok(Some(self.create_image(
+            Some(CreateImageOptions {
+                from_image: String::from(from_image),
+                tag: String::from(tag),
+                ..Default::default()
+            }),
+            None,
+            credentials,
+        )))
     }
 
     /// ---
     ///
-    /// # Search Images
+    /// # Ensure Image Tagged
     ///
-    /// Search for an image on Docker Hub.
+    /// Pull an image if it is not present locally, then point a local alias tag at it. Nothing is
+    /// tagged if the alias already refers to the same image, so this can be reapplied on every
+    /// deploy.
     ///
     /// # Arguments
     ///
-    ///  - [Search Image Options](SearchImagesOptions) struct.
+    ///  - Image name as a string slice, which may include a tag or digest.
+    ///  - Alias as a string slice, for example `base:current`. Without a tag, `latest` is used.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct, used if the image is pulled.
     ///
     /// # Returns
     ///
-    ///  - Vector of [Image Search Response Item](ImageSearchResponseItem) results, wrapped in a
-    ///  Future.
+    ///  - A bool, `true` if the alias was (re)tagged and `false` if it was already up to date,
+    ///    wrapped in a Future.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use bollard::Docker;
-    ///
-    /// use bollard::image::SearchImagesOptions;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.ensure_image_tagged("alpine@sha256:bc41182d7ef5ffc53a40b044e725193bc10142a1243f395ee852a8d9730fc2ad", "base:current", None);
+    /// ```
+    pub async fn ensure_image_tagged(
+        &self,
+        image_name: &str,
+        alias: &str,
+        credentials: Option<DockerCredentials>,
+    ) -> Result<bool, Error> {
+        if let Some(progress) = self.pull_if_absent(image_name, credentials).await? {
+            progress.try_collect::<Vec<_>>().await?;
+        }
+
+        let image = self.inspect_image(image_name).await?;
+
+        match self.inspect_image(alias).await {
+            Ok(current) if current.id.is_some() && current.id == image.id => return Ok(false),
+            Ok(_) => {}
+            Err(e) if e.is_not_found() => {}
+            Err(e) => return Err(e),
+        }
+
+        let (repo, tag) = split_tag(alias).unwrap_or((alias, "latest"));
+
+        self.tag_image(image_name, Some(TagImageOptions { repo, tag }))
+            .await?;
+
+        Ok(true)
+    }
+
+    /// ---
+    ///
+    /// # Prune Images
+    ///
+    /// Delete unused images.
+    ///
+    /// # Arguments
+    ///
+    /// - An optional [Prune Images Options](PruneImagesOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - a [Prune Image Response](ImagePruneResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::PruneImagesOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("until", vec!["10m"]);
+    ///
+    /// let options = Some(PruneImagesOptions {
+    ///   filters
+    /// });
+    ///
+    /// docker.prune_images(options);
+    /// ```
+    pub async fn prune_images<T>(
+        &self,
+        options: Option<PruneImagesOptions<T>>,
+    ) -> Result<ImagePruneResponse, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/images/prune";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Prune Build
+    ///
+    /// Delete the build cache.
+    ///
+    /// # Arguments
+    ///
+    /// - An optional [Prune Build Options](PruneBuildOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - a [Prune Build Response](BuildPruneResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::PruneBuildOptions;
+    ///
+    /// let options = Some(PruneBuildOptions::<String> {
+    ///   all: true,
+    ///   ..Default::default()
+    /// });
+    ///
+    /// docker.prune_build(options);
+    /// ```
+    pub async fn prune_build<T>(
+        &self,
+        options: Option<PruneBuildOptions<T>>,
+    ) -> Result<BuildPruneResponse, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/build/prune";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Image History
+    ///
+    /// Return parent layers of an image.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [History Response Item](HistoryResponseItem), wrapped in a
+    ///    Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.image_history("hello-world");
+    /// ```
+    pub async fn image_history(&self, image_name: &str) -> Result<Vec<HistoryResponseItem>, Error> {
+        let url = format!("/images/{}/history", image_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Image Layers
+    ///
+    /// Break down the size of an image by layer, matching the layers of its root filesystem with
+    /// the entries of its [history](Docker::image_history()).
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Image Layer](ImageLayer), oldest first, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.image_layers("hello-world");
+    /// ```
+    pub async fn image_layers(&self, image_name: &str) -> Result<Vec<ImageLayer>, Error> {
+        let (inspect, history) = future::try_join(
+            self.inspect_image(image_name),
+            self.image_history(image_name),
+        )
+        .await?;
+
+        let layers = inspect
+            .root_fs
+            .and_then(|root_fs| root_fs.layers)
+            .unwrap_or_default();
+
+        Ok(correlate_layers(layers, history))
+    }
+
+    /// ---
+    ///
+    /// # Search Images
+    ///
+    /// Search for an image on Docker Hub.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Search Image Options](SearchImagesOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Image Search Response Item](ImageSearchResponseItem) results, wrapped in a
+    ///    Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    ///
+    /// use bollard::image::SearchImagesOptions;
     /// use std::default::Default;
     /// use std::collections::HashMap;
     ///
@@ -755,7 +1558,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - Vector of [Image Delete Response Item](ImageDeleteResponseItem), wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -859,12 +1662,15 @@ impl Docker {
     /// # Arguments
     ///
     ///  - Image name as a string slice.
-    ///  - Optional [Push Image Options](PushImageOptions) struct.
+    ///  - Optional [Push Image Options](PushImageOptions) struct. If omitted, only the tag
+    ///    embedded in the image name is pushed, e.g. `1.2.3` for `app:1.2.3`.
     ///  - Optional [Docker Credentials](DockerCredentials) struct.
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Push Image Info](PushImageInfo) progress structs, wrapped in a Stream. Registries often
+    ///    reject a push part way through, in which case the stream ends with a [Docker Stream
+    ///    Error](Error::DockerStreamError) carrying the registry's message.
     ///
     /// # Examples
     ///
@@ -898,6 +1704,21 @@ impl Docker {
     where
         T: Into<String> + Serialize,
     {
+        let (image_name, options) = match options {
+            Some(PushImageOptions { tag }) => {
+                (image_name, Some(PushImageOptions { tag: tag.into() }))
+            }
+            None => match split_tag(image_name) {
+                Some((repository, tag)) => (
+                    repository,
+                    Some(PushImageOptions {
+                        tag: String::from(tag),
+                    }),
+                ),
+                None => (image_name, None),
+            },
+        };
+
         let url = format!("/images/{}/push", image_name);
 
         match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
@@ -943,7 +1764,7 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - [Commit](Commit), wrapped in a Future.
+    ///  - [Id Response](IdResponse) with the ID of the new image, wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -971,7 +1792,7 @@ impl Docker {
         &self,
         options: CommitContainerOptions<T>,
         config: Config<Z>,
-    ) -> Result<Commit, Error>
+    ) -> Result<IdResponse, Error>
     where
         T: Into<String> + Serialize,
         Z: Into<String> + Eq + Hash + Serialize,
@@ -1008,7 +1829,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1043,6 +1864,99 @@ impl Docker {
     ) -> impl Stream<Item = Result<BuildInfo, Error>>
     where
         T: Into<String> + Eq + Hash + Serialize,
+    {
+        self.build_image_stream(options, credentials, tar)
+            .map(|res| {
+                if let Ok(BuildInfo {
+                    error: Some(error), ..
+                }) = res
+                {
+                    Err(Error::DockerStreamError { error })
+                } else {
+                    res
+                }
+            })
+    }
+
+    /// ---
+    ///
+    /// # Build Image Vertices
+    ///
+    /// Build an image from a tar archive with a `Dockerfile` in it, decoding the BuildKit traces
+    /// carried in the `aux` field of the build output into the progress of each build step.
+    /// Messages that are not BuildKit traces are skipped, so this yields nothing for builds run by
+    /// the classic builder.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Build Image Options](BuildImageOptions) struct.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///  - Tarball, as described in the [Build Image API](Docker::build_image()).
+    ///
+    /// # Returns
+    ///
+    ///  - [BuildKit Vertex](BuildKitVertex), wrapped in an asynchronous Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::BuildImageOptions;
+    ///
+    /// use futures_util::stream::StreamExt;
+    ///
+    /// let options = BuildImageOptions {
+    ///     dockerfile: "Dockerfile",
+    ///     t: "my-image",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// # let contents = Vec::new();
+    /// async move {
+    ///     let mut vertices = docker.build_image_vertices(options, None, Some(contents.into()));
+    ///     while let Some(Ok(vertex)) = vertices.next().await {
+    ///         println!("{} cached={}", vertex.name, vertex.cached);
+    ///     }
+    /// };
+    /// ```
+    pub fn build_image_vertices<T>(
+        &self,
+        options: BuildImageOptions<T>,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        tar: Option<Body>,
+    ) -> impl Stream<Item = Result<BuildKitVertex, Error>>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        self.build_image_stream(options, credentials, tar)
+            .map(|res| match res {
+                Ok(BuildKitMessage {
+                    error: Some(error), ..
+                }) => vec![Err(Error::DockerStreamError { error })],
+                Ok(BuildKitMessage {
+                    id: Some(id),
+                    aux: Some(serde_json::Value::String(aux)),
+                    ..
+                }) if id == BUILDKIT_TRACE_ID => match decode_buildkit_trace(&aux) {
+                    Ok(vertices) => vertices.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                },
+                Ok(_) => vec![],
+                Err(e) => vec![Err(e)],
+            })
+            .flat_map(stream::iter)
+    }
+
+    fn build_image_stream<T, R>(
+        &self,
+        options: BuildImageOptions<T>,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        tar: Option<Body>,
+    ) -> impl Stream<Item = Result<R, Error>>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+        R: DeserializeOwned + Send + 'static,
     {
         let url = "/build";
 
@@ -1062,16 +1976,6 @@ impl Docker {
             }
             Err(e) => stream::once(async move { Err(e.into()) }).boxed(),
         }
-        .map(|res| {
-            if let Ok(BuildInfo {
-                error: Some(error), ..
-            }) = res
-            {
-                Err(Error::DockerStreamError { error })
-            } else {
-                res
-            }
-        })
     }
 
     /// ---
@@ -1107,6 +2011,142 @@ impl Docker {
         self.process_into_body(req)
     }
 
+    /// ---
+    ///
+    /// # Export Images
+    ///
+    /// Get a single tarball containing several images and their metadata, the equivalent of
+    /// `docker save` with multiple names. Layers shared between the images are only included
+    /// once.
+    ///
+    /// # Arguments
+    ///
+    ///  - A slice of image names, each an image and tag (e.g. `alpine:latest`), a repository or an
+    ///    image ID.
+    ///
+    /// # Returns
+    ///
+    ///  - An uncompressed TAR archive, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.export_images(&["alpine:latest", "hello-world:latest"]);
+    /// ```
+    pub fn export_images(&self, image_names: &[&str]) -> impl Stream<Item = Result<Bytes, Error>> {
+        // The `names` parameter is repeated once per image, so the query is serialized from pairs.
+        let query: Vec<_> = image_names
+            .iter()
+            .map(|name| ("names", String::from(*name)))
+            .collect();
+
+        let req = self.build_request(
+            "/images/get",
+            Builder::new()
+                .method(Method::GET)
+                .header(CONTENT_TYPE, "application/json"),
+            Some(query),
+            Ok(Body::empty()),
+        );
+        self.process_into_body(req)
+    }
+
+    /// ---
+    ///
+    /// # Copy Image To Host Tar
+    ///
+    /// Export an image with the [Export Image API](Docker::export_image()) and write the archive
+    /// to `writer`, optionally verifying its content while it is streamed.
+    ///
+    /// A repository digest is the digest of the registry manifest, which is not part of an
+    /// exported archive. Verification instead checks the content addressed digests the manifest
+    /// refers to: the SHA-256 of the image configuration has to match the image ID, and the SHA-256
+    /// of every layer has to match the diff IDs of the image's root filesystem, as reported by the
+    /// [Inspect Image API](Docker::inspect_image()).
+    ///
+    /// The image ID reported by the daemon only guards against an archive corrupted on its way
+    /// to the writer. Pass the image ID the image is expected to have, for example one recorded
+    /// when it was built, to also check that the daemon exported that image.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - Writer for the archive.
+    ///  - Whether to verify the exported content.
+    ///  - Optional image ID, such as `sha256:...`, the image configuration has to match instead of
+    ///    the ID reported by the daemon. Giving it implies verification.
+    ///
+    /// # Returns
+    ///
+    ///  - The number of bytes written, wrapped in a Future. An [Image Digest Mismatch
+    ///    Error](Error::ImageDigestMismatchError) is returned if verification fails, after the
+    ///    archive has been written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async {
+    ///     let mut archive = Vec::new();
+    ///     docker.copy_image_to_host_tar("hello-world", &mut archive, true, None).await.unwrap();
+    /// };
+    /// ```
+    pub async fn copy_image_to_host_tar<W>(
+        &self,
+        image_name: &str,
+        writer: &mut W,
+        verify: bool,
+        expected_id: Option<&str>,
+    ) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        // The archive is read by the tar crate on a blocking thread, fed with the chunks as they
+        // are written.
+        let verification = if verify || expected_id.is_some() {
+            let image = self.inspect_image(image_name).await?;
+            let image_id = expected_id
+                .map(String::from)
+                .unwrap_or_else(|| image.id.unwrap_or_default());
+            let diff_ids = image
+                .root_fs
+                .and_then(|root_fs| root_fs.layers)
+                .unwrap_or_default();
+
+            let (sender, receiver) = mpsc::channel();
+            let task = tokio::task::spawn_blocking(move || {
+                read_archive(ChunkReader::new(receiver))?.verify(&image_id, &diff_ids)
+            });
+            Some((sender, task))
+        } else {
+            None
+        };
+
+        let mut stream = self.export_image(image_name);
+        let mut written = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            if let Some((sender, _)) = &verification {
+                // The reader stops at the end of the archive, ignoring any trailing data.
+                let _ = sender.send(chunk.clone());
+            }
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        if let Some((sender, task)) = verification {
+            drop(sender);
+            task.await.map_err(io::Error::other)??;
+        }
+
+        Ok(written)
+    }
+
     /// ---
     ///
     /// # Import Image
@@ -1122,7 +2162,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [Build Info](BuildInfo), wrapped in an asynchronous
-    ///  Stream.
+    ///    Stream.
     ///
     /// # Examples
     ///
@@ -1149,9 +2189,9 @@ impl Docker {
     ///         let bytes = r.unwrap().freeze();
     ///         Ok::<_, Error>(bytes)
     ///     });
-
+    ///
     ///     let body = hyper::Body::wrap_stream(byte_stream);
-
+    ///
     ///     let mut stream = docker
     ///         .import_image(
     ///             ImportImageOptions {
@@ -1202,19 +2242,418 @@ impl Docker {
 
 #[cfg(test)]
 mod tests {
-    #![cfg(not(target_arch = "windows"))]
-
     use std::io::Write;
 
     use futures_util::TryStreamExt;
     use yup_hyper_mock::HostToReplyConnector;
 
     use crate::{
+        container::Config,
         image::{BuildImageOptions, PushImageOptions},
-        Docker, API_DEFAULT_VERSION,
+        test_server, Docker, API_DEFAULT_VERSION,
+    };
+
+    use super::{
+        correlate_layers, split_tag, BuildContext, BuildKitVertex, CommitContainerOptions,
+        CreateImageOptions, ListImagesOptions, PruneImagesOptions, RemoveImageOptions,
     };
+    use crate::models::{HistoryResponseItem, ImageDeleteResponseItem, ProgressDetail};
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_build_image_options_cachefrom() {
+        let options = BuildImageOptions {
+            cachefrom: vec!["app:latest", "app:1.2.3"],
+            ..Default::default()
+        };
+
+        let query = serde_urlencoded::to_string(options).unwrap();
+
+        assert!(query.contains("cachefrom=%5B%22app%3Alatest%22%2C%22app%3A1.2.3%22%5D"));
+    }
+
+    #[test]
+    fn test_list_images_options_dangling() {
+        let mut filters = HashMap::new();
+        filters.insert("dangling", vec!["true"]);
+
+        let options = ListImagesOptions {
+            all: true,
+            filters,
+            digests: true,
+        };
+
+        assert_eq!(
+            serde_urlencoded::to_string(options).unwrap(),
+            "all=true&filters=%7B%22dangling%22%3A%5B%22true%22%5D%7D&digests=true"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_image_tagged_up_to_date() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"sha256:abc\",\"RepoTags\":[\"base:current\",\"alpine:3.16\"]}".to_string(),
+        );
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let tagged = docker
+            .ensure_image_tagged("alpine:3.16", "base:current", None)
+            .await
+            .unwrap();
 
-    use super::CreateImageOptions;
+        assert!(!tagged);
+    }
+
+    #[tokio::test]
+    async fn test_import_image_from_tar() {
+        let (docker, requests) =
+            test_server::serve(|_| ("200 OK", String::from("{\"status\":\"sha256:abc\"}\n"))).await;
+
+        let progress = docker
+            .import_image_from_tar(
+                Vec::from(&b"rootfs"[..]).into(),
+                "base",
+                "latest",
+                vec!["CMD [\"/bin/sh\"]", "ENV LANG=C"],
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            progress.last().and_then(|info| info.status.as_deref()),
+            Some("sha256:abc")
+        );
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![format!(
+                "POST /v{}/images/create?fromSrc=-&repo=base&tag=latest\
+                 &changes=CMD+%5B%22%2Fbin%2Fsh%22%5D&changes=ENV+LANG%3DC",
+                API_DEFAULT_VERSION
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_container() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 201 Created\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"sha256:4b5d6e7f8a9c\"}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .commit_container(
+                CommitContainerOptions {
+                    container: "db",
+                    repo: "db-snapshot",
+                    tag: "v1",
+                    changes: Some("CMD [\"postgres\"]"),
+                    ..Default::default()
+                },
+                Config::<String> {
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.id, "sha256:4b5d6e7f8a9c");
+    }
+
+    #[test]
+    fn test_commit_container_options_pause() {
+        let options = CommitContainerOptions {
+            container: "db",
+            author: "ops",
+            ..Default::default()
+        };
+
+        assert!(options.pause);
+        assert_eq!(
+            serde_urlencoded::to_string(CommitContainerOptions {
+                pause: false,
+                ..options
+            })
+            .unwrap(),
+            "container=db&repo=&tag=&comment=&author=ops&pause=false"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_image_vertices() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"id\":\"moby.image.id\",\"aux\":{\"ID\":\"sha256:c\"}}\n{\"id\":\"moby.buildkit.trace\",\"aux\":\"Ci4KCHNoYTI1NjphEghzaGEyNTY6YhoOWzIvMl0gUlVOIG1ha2UgASoGCICg+PoFGgoKCHNoYTI1Njph\"}\n".to_string(),
+        );
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let vertices = docker
+            .build_image_vertices(BuildImageOptions::<&str>::default(), None, None)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vertices,
+            vec![BuildKitVertex {
+                digest: String::from("sha256:a"),
+                inputs: vec![String::from("sha256:b")],
+                name: String::from("[2/2] RUN make"),
+                started: Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+                completed: None,
+                cached: true,
+                error: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_context_dockerignore() {
+        let mut context = BuildContext::new();
+        context
+            .dockerignore("# comment\ntarget\n**/*.log\n!keep.log\n")
+            .add_file("Dockerfile", b"FROM alpine")
+            .unwrap()
+            .add_file("target/debug/app", b"binary")
+            .unwrap()
+            .add_file("logs/build.log", b"log")
+            .unwrap()
+            .add_file("keep.log", b"log")
+            .unwrap()
+            .add_file("src/main.rs", b"fn main() {}")
+            .unwrap();
+
+        let bytes = hyper::body::to_bytes(context.finish().unwrap())
+            .await
+            .unwrap();
+        let mut archive = tar::Archive::new(&bytes[..]);
+        let paths = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                entry
+                    .unwrap()
+                    .path()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, vec!["Dockerfile", "keep.log", "src/main.rs"]);
+    }
+
+    fn history_item(created_by: &str, size: i64) -> HistoryResponseItem {
+        HistoryResponseItem {
+            id: String::from("<missing>"),
+            created: 0,
+            created_by: String::from(created_by),
+            tags: vec![],
+            size,
+            comment: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_correlate_layers() {
+        let layers = vec![
+            String::from("sha256:base"),
+            String::from("sha256:empty"),
+            String::from("sha256:app"),
+        ];
+        let history = vec![
+            history_item("/bin/sh -c #(nop)  CMD [\"/app\"]", 0),
+            history_item("/bin/sh -c #(nop) COPY file:abc in /app ", 2048),
+            history_item("/bin/sh -c mkdir /data", 0),
+            history_item("/bin/sh -c #(nop)  ENV PATH=/bin", 0),
+            history_item("/bin/sh -c #(nop) ADD file:def in / ", 1024),
+        ];
+
+        let result = correlate_layers(layers, history);
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|layer| (layer.digest.as_str(), layer.size))
+                .collect::<Vec<_>>(),
+            vec![
+                ("sha256:base", Some(1024)),
+                ("sha256:empty", Some(0)),
+                ("sha256:app", Some(2048)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_context_may_include_below() {
+        let mut context = BuildContext::new();
+        context.dockerignore("target\nvendor\n!vendor/keep/*.rs\n");
+
+        assert!(!context.may_include_below("target"));
+        assert!(context.may_include_below("vendor"));
+        assert!(context.may_include_below("vendor/keep"));
+        assert!(!context.may_include_below("vendor/keep/main.rs"));
+        assert!(!context.may_include_below("vendor/other"));
+
+        let mut context = BuildContext::new();
+        context.dockerignore("**/*.log\n!**/keep.log\n");
+        assert!(context.may_include_below("logs"));
+    }
+
+    #[test]
+    fn test_split_tag() {
+        assert_eq!(split_tag("app:1.2.3"), Some(("app", "1.2.3")));
+        assert_eq!(
+            split_tag("localhost:5000/app:latest"),
+            Some(("localhost:5000/app", "latest"))
+        );
+        assert_eq!(split_tag("localhost:5000/app"), None);
+        assert_eq!(split_tag("app"), None);
+        assert_eq!(split_tag("app@sha256:abcdef"), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_image_progress() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"status\":\"Pulling from library/hello-world\",\"id\":\"latest\"}\n{\"status\":\"Downloading\",\"progressDetail\":{\"current\":1024,\"total\":2479},\"progress\":\"[=====>     ]\",\"id\":\"2db29710123e\"}\n{\"status\":\"Status: Downloaded newer image for hello-world:latest\"}\n".to_string());
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: "hello-world",
+                    tag: "latest",
+                    ..Default::default()
+                }),
+                None,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(
+            result[1].progress_detail,
+            Some(ProgressDetail {
+                current: Some(1024),
+                total: Some(2479),
+            })
+        );
+        assert_eq!(
+            result[2].status.as_deref(),
+            Some("Status: Downloaded newer image for hello-world:latest")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_image_untagged_and_deleted() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n[{\"Untagged\":\"bollard-alias:latest\"},{\"Deleted\":\"sha256:feb5d9fea6a5\"}]".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .remove_image(
+                "bollard-alias:latest",
+                Some(RemoveImageOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                ImageDeleteResponseItem {
+                    untagged: Some(String::from("bollard-alias:latest")),
+                    deleted: None,
+                },
+                ImageDeleteResponseItem {
+                    untagged: None,
+                    deleted: Some(String::from("sha256:feb5d9fea6a5")),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_images_dangling() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"ImagesDeleted\":[{\"Deleted\":\"sha256:0b1e6bd6d6d5\"}],\"SpaceReclaimed\":4096}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let mut filters = HashMap::new();
+        filters.insert("dangling", vec!["true"]);
+        filters.insert("until", vec!["24h"]);
+
+        let result = docker
+            .prune_images(Some(PruneImagesOptions { filters }))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.images_deleted,
+            Some(vec![ImageDeleteResponseItem {
+                untagged: None,
+                deleted: Some(String::from("sha256:0b1e6bd6d6d5")),
+            }])
+        );
+        assert_eq!(result.space_reclaimed, Some(4096));
+    }
+
+    #[tokio::test]
+    async fn test_export_images() {
+        let (docker, requests) =
+            test_server::serve(|_| ("200 OK", String::from("tar-content"))).await;
+
+        let archive = docker
+            .export_images(&["alpine:latest", "hello-world:latest"])
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(archive.concat(), b"tar-content");
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![format!(
+                "GET /v{}/images/get?names=alpine%3Alatest&names=hello-world%3Alatest",
+                API_DEFAULT_VERSION
+            )]
+        );
+    }
 
     #[tokio::test]
     async fn test_create_image_with_error() {
@@ -1267,10 +2706,54 @@ mod tests {
 
         assert!(matches!(
             result,
-            Err(crate::errors::Error::DockerStreamError { error: _ })
+            Err(crate::errors::Error::DockerStreamError { ref error }) if error == "EOF"
         ));
     }
 
+    #[tokio::test]
+    async fn test_build_image_aux_id() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"stream\":\"Step 1/1 : FROM scratch\\n\"}\n{\"stream\":\" ---\\u003e \\n\"}\n{\"aux\":{\"ID\":\"sha256:471a1b8817eefca4b1d6bbc08d2f5ba5e5b6c0fb8b9bce7b1d8ba3e7d3f1f2a0\"}}\n{\"stream\":\"Successfully built 471a1b8817ee\\n\"}\n".to_string());
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let dockerfile = b"FROM scratch\n";
+
+        let mut header = tar::Header::new_gnu();
+        header.set_path("Dockerfile").unwrap();
+        header.set_size(dockerfile.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        let mut tar = tar::Builder::new(Vec::new());
+        tar.append(&header, &dockerfile[..]).unwrap();
+
+        let result = docker
+            .build_image(
+                BuildImageOptions {
+                    dockerfile: "Dockerfile",
+                    t: "bollard-scratch",
+                    ..Default::default()
+                },
+                None,
+                Some(tar.into_inner().unwrap().into()),
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .filter_map(|info| info.aux.as_ref())
+                .filter_map(|aux| aux.id.as_deref())
+                .next_back(),
+            Some("sha256:471a1b8817eefca4b1d6bbc08d2f5ba5e5b6c0fb8b9bce7b1d8ba3e7d3f1f2a0")
+        );
+    }
+
     #[tokio::test]
     async fn test_build_image_with_error() {
         let mut connector = HostToReplyConnector::default();
@@ -1319,4 +2802,21 @@ mod tests {
             Err(crate::errors::Error::DockerStreamError { error: _ })
         ));
     }
+
+    #[tokio::test]
+    async fn test_pull_if_absent_present() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Id\":\"sha256:abc\"}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker.pull_if_absent("hello-world", None).await.unwrap();
+
+        assert!(result.is_none());
+    }
 }
@@ -0,0 +1,359 @@
+//! Verification of the image archives written by `docker save`.
+
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use tar::EntryType;
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::mpsc;
+
+use crate::errors::Error;
+
+/// SHA-256 digests of the regular files in an image archive, along with its manifest.
+#[derive(Debug, Default)]
+pub(crate) struct ArchiveDigests {
+    files: HashMap<String, String>,
+    links: HashMap<String, String>,
+    manifest: Option<Vec<u8>>,
+}
+
+/// Entry of the `manifest.json` file of an image archive.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageArchiveManifest {
+    config: String,
+    layers: Vec<String>,
+}
+
+/// Reader over the chunks of an archive as they are received from the daemon, so that the
+/// synchronous `tar` crate can read an archive while it is streamed.
+pub(crate) struct ChunkReader {
+    chunks: mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl ChunkReader {
+    pub(crate) fn new(chunks: mpsc::Receiver<Bytes>) -> Self {
+        ChunkReader {
+            chunks,
+            chunk: Bytes::new(),
+        }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.chunks.recv() {
+                Ok(chunk) => self.chunk = chunk,
+                // The sender is dropped once the whole archive has been sent.
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk.split_to(n));
+        Ok(n)
+    }
+}
+
+/// Normalize a path in an archive, resolving `.` and `..` components.
+fn normalize_tar_path(path: &Path) -> String {
+    let mut components: Vec<String> = Vec::new();
+    for component in path.to_string_lossy().split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(String::from(component)),
+        }
+    }
+    components.join("/")
+}
+
+/// Read an archive to its end, hashing every regular file.
+pub(crate) fn read_archive<R: Read>(reader: R) -> Result<ArchiveDigests, Error> {
+    // The reader only fails on a malformed archive, most likely one that ended inside a header.
+    let truncated = |_| Error::ImageArchiveTruncatedError {
+        path: String::new(),
+    };
+
+    let mut digests = ArchiveDigests::default();
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().map_err(truncated)? {
+        let mut entry = entry.map_err(truncated)?;
+        let path = normalize_tar_path(&entry.path().map_err(truncated)?);
+
+        match entry.header().entry_type() {
+            EntryType::Regular | EntryType::Continuous => {
+                let size = entry.size();
+                let read = if path == "manifest.json" {
+                    let mut manifest = Vec::new();
+                    let read = entry.read_to_end(&mut manifest)? as u64;
+                    digests.manifest = Some(manifest);
+                    read
+                } else {
+                    let mut hasher = Sha256::new();
+                    let read = io::copy(&mut entry, &mut hasher)?;
+                    digests
+                        .files
+                        .insert(path.clone(), hex::encode(hasher.finalize()));
+                    read
+                };
+                if read < size {
+                    return Err(Error::ImageArchiveTruncatedError { path });
+                }
+            }
+            EntryType::Link => {
+                if let Some(target) = entry.link_name().map_err(truncated)? {
+                    digests.links.insert(path, normalize_tar_path(&target));
+                }
+            }
+            EntryType::Symlink => {
+                if let Some(target) = entry.link_name().map_err(truncated)? {
+                    let target = if target.is_absolute() {
+                        normalize_tar_path(&target)
+                    } else {
+                        let parent = path.rsplit_once('/').map(|(parent, _)| parent);
+                        normalize_tar_path(&Path::new(parent.unwrap_or("")).join(target))
+                    };
+                    digests.links.insert(path, target);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(digests)
+}
+
+impl ArchiveDigests {
+    /// Digest of a file in the archive, following links.
+    fn digest(&self, path: &str) -> Option<&str> {
+        let mut path = normalize_tar_path(Path::new(path));
+        for _ in 0..=self.links.len() {
+            match self.links.get(&path) {
+                Some(target) => path = target.clone(),
+                None => break,
+            }
+        }
+        self.files.get(&path).map(String::as_str)
+    }
+
+    /// Check that the archive contains an image with the given ID, and that its layers match the
+    /// given diff IDs.
+    pub(crate) fn verify(&self, image_id: &str, diff_ids: &[String]) -> Result<(), Error> {
+        let strip = |digest: &str| String::from(digest.trim_start_matches("sha256:"));
+        let mismatch =
+            |path: &str, expected: &str, actual: Option<&str>| Error::ImageDigestMismatchError {
+                path: String::from(path),
+                expected: format!("sha256:{}", strip(expected)),
+                actual: actual
+                    .map(|actual| format!("sha256:{}", actual))
+                    .unwrap_or_else(|| String::from("missing")),
+            };
+
+        let manifest: Vec<ImageArchiveManifest> = match &self.manifest {
+            Some(manifest) => serde_json::from_slice(manifest)?,
+            None => return Err(mismatch("manifest.json", image_id, None)),
+        };
+
+        let expected_id = strip(image_id);
+        let image = manifest
+            .iter()
+            .find(|image| self.digest(&image.config) == Some(expected_id.as_str()))
+            .ok_or_else(|| match manifest.first() {
+                Some(image) => mismatch(&image.config, image_id, self.digest(&image.config)),
+                None => mismatch("manifest.json", image_id, None),
+            })?;
+
+        if image.layers.len() != diff_ids.len() {
+            return Err(Error::ImageDigestMismatchError {
+                path: String::from("manifest.json"),
+                expected: format!("{} layers", diff_ids.len()),
+                actual: format!("{} layers", image.layers.len()),
+            });
+        }
+
+        for (layer, diff_id) in image.layers.iter().zip(diff_ids) {
+            let actual = self.digest(layer);
+            if actual != Some(strip(diff_id).as_str()) {
+                return Err(mismatch(layer, diff_id, actual));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use sha2::{Digest, Sha256};
+
+    use std::sync::mpsc;
+
+    use super::{read_archive, ArchiveDigests, ChunkReader};
+    use crate::errors::Error;
+
+    fn sha256(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    /// Append an entry, letting the tar crate write a GNU long name entry for paths that don't fit
+    /// in the header.
+    fn append(builder: &mut tar::Builder<Vec<u8>>, path: &str, data: &[u8]) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, path, data).unwrap();
+    }
+
+    /// Append an entry whose path is only given in a PAX extended header.
+    fn append_pax(builder: &mut tar::Builder<Vec<u8>>, path: &str, data: &[u8]) {
+        builder
+            .append_pax_extensions([("path", path.as_bytes())])
+            .unwrap();
+        let mut header = tar::Header::new_ustar();
+        header.set_path("pax-placeholder").unwrap();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, data).unwrap();
+    }
+
+    /// An image archive with a single layer stored under `layer_path`.
+    fn image_archive(
+        layer_path: &str,
+        append_layer: fn(&mut tar::Builder<Vec<u8>>, &str, &[u8]),
+    ) -> (Vec<u8>, String, String) {
+        let config = br#"{"architecture":"amd64"}"#;
+        let layer = b"layer contents";
+        let config_digest = sha256(config);
+        let manifest = format!(
+            r#"[{{"Config":"blobs/sha256/{}","Layers":["{}"]}}]"#,
+            config_digest, layer_path
+        );
+
+        let mut builder = tar::Builder::new(Vec::new());
+        append(
+            &mut builder,
+            &format!("blobs/sha256/{}", config_digest),
+            config,
+        );
+        append_layer(&mut builder, layer_path, layer);
+        append(&mut builder, "manifest.json", manifest.as_bytes());
+
+        (
+            builder.into_inner().unwrap(),
+            format!("sha256:{}", config_digest),
+            format!("sha256:{}", sha256(layer)),
+        )
+    }
+
+    /// Read an archive the way it is streamed from the daemon, in small chunks.
+    fn digests(archive: &[u8]) -> Result<ArchiveDigests, Error> {
+        let (sender, receiver) = mpsc::channel();
+        for chunk in archive.chunks(100) {
+            sender.send(Bytes::copy_from_slice(chunk)).unwrap();
+        }
+        drop(sender);
+        read_archive(ChunkReader::new(receiver))
+    }
+
+    #[test]
+    fn test_tar_digests_verify() {
+        let config = br#"{"architecture":"amd64"}"#;
+        let layer = b"layer contents";
+        let (config_digest, layer_digest) = (sha256(config), sha256(layer));
+        let manifest = format!(
+            r#"[{{"Config":"blobs/sha256/{}","Layers":["{}/layer.tar"]}}]"#,
+            config_digest,
+            "a".repeat(64)
+        );
+
+        let mut builder = tar::Builder::new(Vec::new());
+        append(
+            &mut builder,
+            &format!("blobs/sha256/{}", config_digest),
+            config,
+        );
+        append(
+            &mut builder,
+            &format!("blobs/sha256/{}", layer_digest),
+            layer,
+        );
+        append(&mut builder, "manifest.json", manifest.as_bytes());
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder
+            .append_link(
+                &mut header,
+                format!("{}/layer.tar", "a".repeat(64)),
+                format!("../blobs/sha256/{}", layer_digest),
+            )
+            .unwrap();
+        let digests = digests(&builder.into_inner().unwrap()).unwrap();
+
+        let image_id = format!("sha256:{}", config_digest);
+        let diff_id = format!("sha256:{}", layer_digest);
+        assert!(digests.verify(&image_id, &[diff_id]).is_ok());
+
+        let tampered = format!("sha256:{}", sha256(b"other layer"));
+        assert!(matches!(
+            digests.verify(&image_id, &[tampered]),
+            Err(Error::ImageDigestMismatchError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tar_digests_gnu_long_name() {
+        let layer_path = format!("{}/layer.tar", "b".repeat(120));
+        let (archive, image_id, diff_id) = image_archive(&layer_path, append);
+
+        assert!(digests(&archive)
+            .unwrap()
+            .verify(&image_id, &[diff_id])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_tar_digests_pax_path() {
+        let layer_path = format!("{}/layer.tar", "c".repeat(120));
+        let (archive, image_id, diff_id) = image_archive(&layer_path, append_pax);
+
+        let digests = digests(&archive).unwrap();
+        assert!(digests.verify(&image_id, &[diff_id]).is_ok());
+        assert!(digests.digest("pax-placeholder").is_none());
+    }
+
+    #[test]
+    fn test_tar_digests_truncated() {
+        let (archive, _, _) = image_archive("layer/layer.tar", append);
+
+        // Cut the archive in the middle of the layer's data.
+        let header_end = archive
+            .windows(b"layer contents".len())
+            .position(|window| window == b"layer contents")
+            .unwrap();
+        let result = digests(&archive[..header_end + 5]);
+        assert!(matches!(
+            result,
+            Err(Error::ImageArchiveTruncatedError { path }) if path == "layer/layer.tar"
+        ));
+
+        // Cut the archive in the middle of the manifest's header.
+        let manifest_header = archive
+            .windows(b"manifest.json".len())
+            .position(|window| window == b"manifest.json")
+            .unwrap();
+        let result = digests(&archive[..manifest_header + 100]);
+        assert!(matches!(
+            result,
+            Err(Error::ImageArchiveTruncatedError { path }) if path.is_empty()
+        ));
+    }
+}
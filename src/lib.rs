@@ -94,6 +94,9 @@
 //! certificates - `key.pem` for the private key, `cert.pem` for the server certificate and
 //! `ca.pem` for the certificate authority chain.
 //!
+//! As with the docker CLI, `DOCKER_TLS_VERIFY` must be set for `Docker::connect_with_ssl_defaults`
+//! to connect.
+//!
 //! Use the `Docker::connect_with_ssl` method API
 //! to parameterise the interface.
 //!
@@ -250,19 +253,24 @@ extern crate log;
 
 // declare modules
 pub mod auth;
+mod batch;
 pub mod container;
 mod docker;
+pub mod duration;
 pub mod errors;
 pub mod exec;
 pub mod image;
+mod image_archive;
 mod named_pipe;
 pub mod network;
 mod read;
 pub mod service;
 pub mod system;
+#[cfg(test)]
+mod test_server;
 mod uri;
 pub mod volume;
 
 // publicly re-export
-pub use crate::docker::{ClientVersion, Docker, API_DEFAULT_VERSION};
+pub use crate::docker::{ClientVersion, Docker, DockerBuilder, API_DEFAULT_VERSION};
 pub use bollard_stubs::models;
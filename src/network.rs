@@ -46,6 +46,14 @@ where
     pub options: HashMap<T, T>,
     /// User-defined key/value metadata.
     pub labels: HashMap<T, T>,
+    /// Creates a config-only network. Config-only networks are placeholder networks for network
+    /// configurations to be used by other networks, and cannot be used directly to run containers
+    /// or services.
+    pub config_only: bool,
+    /// Specifies the source which will provide the configuration for this network, as a `Network`
+    /// key referencing the name of a config-only network.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub config_from: HashMap<T, T>,
 }
 
 /// Parameters used in the [Inspect Network API](super::Docker::inspect_network())
@@ -114,12 +122,69 @@ where
     ///  - `id=<network-id>` Matches all or part of a network ID.
     ///  - `label=<key>` or `label=<key>=<value>` of a network label.
     ///  - `name=<network-name>` Matches all or part of a network name.
-    ///  - `scope=["swarm"|"global"|"local"]` Filters networks by scope (`swarm`, `global`, or `local`).
+    ///  - `scope=["swarm"|"global"|"local"]` Filters networks by scope (`swarm`, `global`, or
+    ///    `local`), see [NetworkScope] and [ListNetworksOptions::scope].
     ///  - `type=["custom"|"builtin"]` Filters networks by type. The `custom` keyword returns all user-defined networks.
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
 }
 
+impl<T> ListNetworksOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize + From<&'static str>,
+{
+    /// Only list networks of the given scope, replacing any `scope` filter already set.
+    pub fn scope(mut self, scope: NetworkScope) -> Self {
+        self.filters
+            .insert(T::from("scope"), vec![T::from(scope.as_str())]);
+        self
+    }
+}
+
+/// Scope of a network, usable as the `scope` filter of the [List Networks
+/// API](Docker::list_networks())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::network::{ListNetworksOptions, NetworkScope};
+///
+/// ListNetworksOptions::<&str>::default().scope(NetworkScope::Swarm);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkScope {
+    /// Networks local to a single host.
+    Local,
+    /// Networks provided by a driver spanning the whole cluster.
+    Global,
+    /// Networks managed by the swarm.
+    Swarm,
+}
+
+impl NetworkScope {
+    /// The scope as sent to the daemon.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkScope::Local => "local",
+            NetworkScope::Global => "global",
+            NetworkScope::Swarm => "swarm",
+        }
+    }
+}
+
+impl From<NetworkScope> for &'static str {
+    fn from(scope: NetworkScope) -> &'static str {
+        scope.as_str()
+    }
+}
+
+impl From<NetworkScope> for String {
+    fn from(scope: NetworkScope) -> String {
+        String::from(scope.as_str())
+    }
+}
+
 /// Network configuration used in the [Connect Network API](Docker::connect_network())
 #[derive(Debug, Clone, Default, PartialEq, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -178,14 +243,38 @@ where
 {
     /// Filters to process on the prune list, encoded as JSON.
     ///  - `until=<timestamp>` Prune networks created before this timestamp. The `<timestamp>` can be
-    ///  Unix timestamps, date formatted timestamps, or Go duration strings (e.g. `10m`, `1h30m`)
-    ///  computed relative to the daemon machine’s time.
+    ///    Unix timestamps, date formatted timestamps, or Go duration strings (e.g. `10m`, `1h30m`)
+    ///    computed relative to the daemon machine’s time.
     ///  - label (`label=<key>`, `label=<key>=<value>`, `label!=<key>`, or `label!=<key>=<value>`)
-    ///  Prune networks with (or without, in case `label!=...` is used) the specified labels.
+    ///    Prune networks with (or without, in case `label!=...` is used) the specified labels.
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// Catch option combinations the daemon rejects for ingress networks before the round-trip.
+fn validate_create_network<T>(config: &CreateNetworkOptions<T>) -> Result<(), Error>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    if !config.ingress {
+        return Ok(());
+    }
+    if config.attachable {
+        return Err(Error::ValidationError {
+            message: String::from("an ingress network cannot be attachable"),
+        });
+    }
+    match serde_json::to_value(&config.driver)? {
+        serde_json::Value::String(driver) if driver == "overlay" => Ok(()),
+        driver => Err(Error::ValidationError {
+            message: format!(
+                "an ingress network requires the overlay driver, got {}",
+                driver
+            ),
+        }),
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -200,7 +289,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Network Create Response](NetworkCreateResponse) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -226,6 +315,8 @@ impl Docker {
     where
         T: Into<String> + Eq + Hash + Serialize,
     {
+        validate_create_network(&config)?;
+
         let url = "/networks/create";
 
         let req = self.build_request(
@@ -282,7 +373,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Models](Network) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -321,6 +412,43 @@ impl Docker {
         self.process_into_value(req).await
     }
 
+    /// ---
+    ///
+    /// # Network Containers
+    ///
+    /// List the containers attached to a network, using the [Inspect Network
+    /// API](Docker::inspect_network()).
+    ///
+    /// # Arguments
+    ///
+    ///  - Network name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of container IDs and their [Network Container](NetworkContainer) endpoint, sorted
+    ///    by container ID, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.network_containers("my_network_name");
+    /// ```
+    pub async fn network_containers(
+        &self,
+        network_name: &str,
+    ) -> Result<Vec<(String, NetworkContainer)>, Error> {
+        let network = self
+            .inspect_network(network_name, None::<InspectNetworkOptions<String>>)
+            .await?;
+
+        let mut containers: Vec<_> = network.containers.unwrap_or_default().into_iter().collect();
+        containers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(containers)
+    }
+
     /// ---
     ///
     /// # List Networks
@@ -332,7 +460,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A vector of [Network](Network) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -530,3 +658,209 @@ impl Docker {
         self.process_into_value(req).await
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use std::collections::HashMap;
+
+    use super::{
+        ConnectNetworkOptions, CreateNetworkOptions, ListNetworksOptions, NetworkScope,
+        PruneNetworksOptions,
+    };
+    use crate::models::{EndpointIpamConfig, EndpointSettings};
+
+    #[test]
+    fn test_list_networks_scope_filter() {
+        let mut filters = HashMap::new();
+        filters.insert("scope", vec!["local"]);
+        let options = ListNetworksOptions { filters }.scope(NetworkScope::Swarm);
+
+        assert_eq!(
+            serde_urlencoded::to_string(options).unwrap(),
+            "filters=%7B%22scope%22%3A%5B%22swarm%22%5D%7D"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_networks() {
+        let mut filters = HashMap::new();
+        filters.insert("label!", vec!["keep"]);
+        let options = PruneNetworksOptions { filters };
+
+        assert_eq!(
+            serde_urlencoded::to_string(&options).unwrap(),
+            "filters=%7B%22label%21%22%3A%5B%22keep%22%5D%7D"
+        );
+
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"NetworksDeleted\":[\"ci-1\",\"ci-2\"]}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker.prune_networks(Some(options)).await.unwrap();
+
+        assert_eq!(
+            result.networks_deleted,
+            Some(vec![String::from("ci-1"), String::from("ci-2")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_network_containers() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Name\":\"backend\",\"Containers\":{\"def\":{\"Name\":\"web\",\"IPv4Address\":\"172.18.0.3/16\"},\"abc\":{\"Name\":\"db\",\"IPv4Address\":\"172.18.0.2/16\"}}}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let containers = docker.network_containers("backend").await.unwrap();
+
+        assert_eq!(
+            containers
+                .iter()
+                .map(|(id, container)| (id.as_str(), container.name.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![("abc", Some("db")), ("def", Some("web"))]
+        );
+    }
+
+    #[test]
+    fn test_connect_network_options() {
+        let options = ConnectNetworkOptions {
+            container: "web",
+            endpoint_config: EndpointSettings {
+                ipam_config: Some(EndpointIpamConfig {
+                    ipv4_address: Some(String::from("10.10.10.101")),
+                    link_local_i_ps: Some(vec![String::from("169.254.0.10")]),
+                    ..Default::default()
+                }),
+                aliases: Some(vec![String::from("frontend")]),
+                links: Some(vec![String::from("db:database")]),
+                mac_address: Some(String::from("02:42:ac:11:00:02")),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(options).unwrap(),
+            serde_json::json!({
+                "Container": "web",
+                "EndpointConfig": {
+                    "IPAMConfig": {
+                        "IPv4Address": "10.10.10.101",
+                        "LinkLocalIPs": ["169.254.0.10"],
+                    },
+                    "Links": ["db:database"],
+                    "Aliases": ["frontend"],
+                    "MacAddress": "02:42:ac:11:00:02",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_connect_network_options_ipv6() {
+        let options = ConnectNetworkOptions {
+            container: "web",
+            endpoint_config: EndpointSettings {
+                ipam_config: Some(EndpointIpamConfig {
+                    ipv6_address: Some(String::from("2001:db8:1::10")),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(options).unwrap(),
+            serde_json::json!({
+                "Container": "web",
+                "EndpointConfig": {
+                    "IPAMConfig": {
+                        "IPv6Address": "2001:db8:1::10",
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_network_config_from() {
+        let config_only = serde_json::to_value(CreateNetworkOptions {
+            name: "config",
+            config_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(config_only["ConfigOnly"], true);
+        assert!(config_only.get("ConfigFrom").is_none());
+
+        let mut config_from = HashMap::new();
+        config_from.insert("Network", "config");
+
+        let network = serde_json::to_value(CreateNetworkOptions {
+            name: "network",
+            driver: "macvlan",
+            config_from,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(network["ConfigOnly"], false);
+        assert_eq!(network["ConfigFrom"]["Network"], "config");
+    }
+
+    #[tokio::test]
+    async fn test_create_network_ingress_validation() {
+        let docker = Docker::connect_with_mock(
+            HostToReplyConnector::default(),
+            "127.0.0.1".to_string(),
+            5,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let attachable = docker
+            .create_network(CreateNetworkOptions {
+                name: "ingress",
+                driver: "overlay",
+                ingress: true,
+                attachable: true,
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(
+            attachable,
+            Err(crate::errors::Error::ValidationError { .. })
+        ));
+
+        let bridge = docker
+            .create_network(CreateNetworkOptions {
+                name: "ingress",
+                driver: "bridge",
+                ingress: true,
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(
+            bridge,
+            Err(crate::errors::Error::ValidationError { .. })
+        ));
+    }
+}
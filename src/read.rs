@@ -107,7 +107,7 @@ impl<T> JsonLineDecoder<T> {
 fn decode_json_from_slice<T: DeserializeOwned>(slice: &[u8]) -> Result<Option<T>, Error> {
     debug!(
         "Decoding JSON line from stream: {}",
-        String::from_utf8_lossy(slice).to_string()
+        String::from_utf8_lossy(slice)
     );
 
     match serde_json::from_slice(slice) {
@@ -235,10 +235,7 @@ where
                         return Poll::Pending;
                     }
                     Poll::Ready(Some(Err(e))) => {
-                        return Poll::Ready(Err(io::Error::new(
-                            io::ErrorKind::Other,
-                            e.to_string(),
-                        )));
+                        return Poll::Ready(Err(io::Error::other(e.to_string())));
                     }
                 },
             }
@@ -257,7 +254,8 @@ mod tests {
     use bytes::{BufMut, BytesMut};
     use tokio_util::codec::Decoder;
 
-    use super::JsonLineDecoder;
+    use super::{JsonLineDecoder, NewlineLogOutputDecoder};
+    use crate::container::LogOutput;
 
     #[test]
     fn json_decode_empty() {
@@ -347,6 +345,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn log_output_partial_decode() {
+        let mut buf = BytesMut::from(&b"\x01\x00\x00"[..]);
+        let mut codec = NewlineLogOutputDecoder::new();
+
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        buf.put(&b"\x00\x00\x00\x00\x06hel"[..]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        buf.put(&b"lo\n\x02\x00\x00\x00\x00\x00\x00\x04err\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap(),
+            Some(LogOutput::StdOut { message }) if message == "hello\n"
+        ));
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap(),
+            Some(LogOutput::StdErr { message }) if message == "err\n"
+        ));
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert!(buf.is_empty());
+    }
+
     #[test]
     fn json_decode_lacking_newline() {
         let mut buf = BytesMut::from(&b"{}"[..]);
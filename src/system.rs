@@ -1,6 +1,8 @@
 //! System API: interface for interacting with the Docker server and/or Registry.
 
 use futures_core::Stream;
+use futures_util::future;
+use futures_util::stream::TryStreamExt;
 use http::request::Builder;
 use hyper::{Body, Method};
 use serde::ser::Serialize;
@@ -10,8 +12,12 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use super::Docker;
+use crate::container::{ListContainersOptions, PruneContainersOptions};
 use crate::errors::Error;
+use crate::image::{ListImagesOptions, PruneBuildOptions, PruneImagesOptions};
 use crate::models::*;
+use crate::network::{ListNetworksOptions, PruneNetworksOptions};
+use crate::volume::{ListVolumesOptions, PruneVolumesOptions};
 
 /// Response of Engine API: GET \"/version\"
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -51,7 +57,7 @@ pub struct Version {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub go_version: Option<String>,
 
-    /// The operating system that the daemon is running on (\"linux\" or \"windows\")
+    /// The operating system that the daemon is running on (`linux` or `windows`)
     #[serde(rename = "Os")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub os: Option<String>,
@@ -97,6 +103,19 @@ pub struct VersionComponents {
     pub details: Option<HashMap<String, Value>>,
 }
 
+/// Headers of the Engine API response: `GET /_ping`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PingResults {
+    /// The default (and highest) API version that is supported by the daemon
+    pub api_version: Option<String>,
+    /// The operating system that the daemon is running on (`linux` or `windows`)
+    pub os_type: Option<String>,
+    /// Whether experimental features are enabled on the daemon
+    pub experimental: bool,
+    /// The default builder of the daemon, `1` for the classic builder or `2` for BuildKit
+    pub builder_version: Option<String>,
+}
+
 /// Parameters used in the [Events API](Docker::events())
 ///
 /// ## Examples
@@ -160,6 +179,178 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// Lifecycle transition of a single container, as emitted by the [Container Lifecycle
+/// API](Docker::container_lifecycle())
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// The container was created.
+    Created,
+    /// The container was started.
+    Started,
+    /// The container's healthcheck reported a healthy status.
+    Healthy,
+    /// The container's healthcheck reported an unhealthy status.
+    Unhealthy,
+    /// The container's main process exited.
+    Died {
+        /// Exit code of the main process, taken from the event's `exitCode` attribute.
+        exit_code: Option<i64>,
+    },
+    /// The container was removed.
+    Removed,
+}
+
+impl LifecycleEvent {
+    fn from_event_message(event: &EventMessage) -> Option<LifecycleEvent> {
+        match event.action.as_deref()? {
+            "create" => Some(LifecycleEvent::Created),
+            "start" => Some(LifecycleEvent::Started),
+            "health_status: healthy" => Some(LifecycleEvent::Healthy),
+            "health_status: unhealthy" => Some(LifecycleEvent::Unhealthy),
+            "die" => Some(LifecycleEvent::Died {
+                exit_code: event
+                    .actor
+                    .as_ref()
+                    .and_then(|actor| actor.attributes.as_ref())
+                    .and_then(|attributes| attributes.get("exitCode"))
+                    .and_then(|code| code.parse().ok()),
+            }),
+            "destroy" => Some(LifecycleEvent::Removed),
+            _ => None,
+        }
+    }
+}
+
+/// Aggregated disk usage, as returned by the [Data Usage Summary
+/// API](Docker::data_usage_summary()). All sizes are in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskSummary {
+    /// Total size of all image layers, counting layers shared between images once.
+    pub images_bytes: i64,
+    /// Total size of the writable layers of all containers.
+    pub containers_bytes: i64,
+    /// Total size of all local volumes.
+    pub volumes_bytes: i64,
+    /// Total size of the build cache, excluding shared records.
+    pub build_cache_bytes: i64,
+    /// Sum of the above.
+    pub total_bytes: i64,
+    /// Space that can be freed by removing unused images, stopped containers, unreferenced
+    /// volumes and unused build cache. Layers shared with another image are not counted for an
+    /// unused image.
+    pub reclaimable_bytes: i64,
+}
+
+impl From<&SystemDataUsageResponse> for DiskSummary {
+    fn from(usage: &SystemDataUsageResponse) -> DiskSummary {
+        let images = usage.images.as_deref().unwrap_or_default();
+        let containers = usage.containers.as_deref().unwrap_or_default();
+        let volumes = usage.volumes.as_deref().unwrap_or_default();
+        let build_cache = usage.build_cache.as_deref().unwrap_or_default();
+
+        // Layers can be shared between images, so the total is taken from the deduplicated
+        // `LayersSize`. Only the layers unique to unused images are counted as reclaimable,
+        // since a shared layer stays on disk while any image still refers to it.
+        let images_bytes = usage.layers_size.unwrap_or_default();
+        let images_reclaimable: i64 = images
+            .iter()
+            .filter(|image| image.containers == 0 && image.size >= 0 && image.shared_size >= 0)
+            .map(|image| image.size - image.shared_size)
+            .sum();
+
+        let containers_bytes = containers
+            .iter()
+            .filter_map(|container| container.size_rw)
+            .sum();
+        let containers_reclaimable: i64 = containers
+            .iter()
+            .filter(|container| container.state.as_deref() != Some("running"))
+            .filter_map(|container| container.size_rw)
+            .sum();
+
+        let volume_usage = || {
+            volumes
+                .iter()
+                .filter_map(|volume| volume.usage_data.as_ref())
+                .filter(|usage| usage.size >= 0)
+        };
+        let volumes_bytes = volume_usage().map(|usage| usage.size).sum();
+        let volumes_reclaimable: i64 = volume_usage()
+            .filter(|usage| usage.ref_count == 0)
+            .map(|usage| usage.size)
+            .sum();
+
+        let build_cache_records = || {
+            build_cache
+                .iter()
+                .filter(|record| !record.shared.unwrap_or_default())
+        };
+        let build_cache_bytes = build_cache_records().filter_map(|record| record.size).sum();
+        let build_cache_reclaimable: i64 = build_cache_records()
+            .filter(|record| !record.in_use.unwrap_or_default())
+            .filter_map(|record| record.size)
+            .sum();
+
+        DiskSummary {
+            images_bytes,
+            containers_bytes,
+            volumes_bytes,
+            build_cache_bytes,
+            total_bytes: images_bytes + containers_bytes + volumes_bytes + build_cache_bytes,
+            reclaimable_bytes: images_reclaimable
+                + containers_reclaimable
+                + volumes_reclaimable
+                + build_cache_reclaimable,
+        }
+    }
+}
+
+/// Objects that a system prune would remove, as returned by the [System Prune Dry Run
+/// API](Docker::system_prune_dry_run())
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PruneCandidates {
+    /// Dangling images.
+    pub images: Vec<ImageSummary>,
+    /// Containers that are not running.
+    pub containers: Vec<ContainerSummary>,
+    /// Custom networks not used by any container.
+    pub networks: Vec<Network>,
+    /// Volumes not referenced by any container.
+    pub volumes: Vec<Volume>,
+    /// Estimated space freed by the prune, in bytes. Volume sizes are only counted when the
+    /// daemon reports usage data for them.
+    pub reclaimable_bytes: i64,
+}
+
+/// Result of every category of a full prune, as returned by the [Prune All With Report
+/// API](Docker::prune_all_with_report())
+#[derive(Debug)]
+pub struct FullPruneReport {
+    /// Result of pruning stopped containers.
+    pub containers: Result<ContainerPruneResponse, Error>,
+    /// Result of pruning unused networks.
+    pub networks: Result<NetworkPruneResponse, Error>,
+    /// Result of pruning unused volumes.
+    pub volumes: Result<VolumePruneResponse, Error>,
+    /// Result of pruning all unused images, not just dangling ones.
+    pub images: Result<ImagePruneResponse, Error>,
+    /// Result of pruning the build cache.
+    pub build_cache: Result<BuildPruneResponse, Error>,
+    /// Total space reclaimed by the categories that succeeded, in bytes.
+    pub space_reclaimed: i64,
+}
+
+impl FullPruneReport {
+    /// Whether every category was pruned without error.
+    pub fn is_ok(&self) -> bool {
+        self.containers.is_ok()
+            && self.networks.is_ok()
+            && self.volumes.is_ok()
+            && self.images.is_ok()
+            && self.build_cache.is_ok()
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -249,6 +440,51 @@ impl Docker {
         self.process_into_string(req).await
     }
 
+    /// ---
+    ///
+    /// # Ping Info
+    ///
+    /// Ping the server like the [Ping API](Docker::ping()), returning what the server reports
+    /// about itself in the response headers instead of the `OK` body.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Ping Results](PingResults) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.ping_info();
+    /// ```
+    pub async fn ping_info(&self) -> Result<PingResults, Error> {
+        let url = "/_ping";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(Body::empty()),
+        );
+
+        let headers = self.process_into_headers(req).await?;
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+
+        Ok(PingResults {
+            api_version: header("API-Version"),
+            os_type: header("OSType"),
+            experimental: header("Docker-Experimental").as_deref() == Some("true"),
+            builder_version: header("Builder-Version"),
+        })
+    }
+
     /// ---
     ///
     /// # Events
@@ -258,7 +494,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - [System Events Response](SystemEventsResponse),
-    ///  wrapped in a Stream.
+    ///    wrapped in a Stream.
     ///
     /// # Examples
     ///
@@ -295,6 +531,48 @@ impl Docker {
         self.process_into_stream(req)
     }
 
+    /// ---
+    ///
+    /// # Container Lifecycle
+    ///
+    /// Stream the lifecycle transitions of a single container, built on top of the [Events
+    /// API](Docker::events()). Events that do not correspond to a [Lifecycle
+    /// Event](LifecycleEvent) are skipped.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name or ID as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Lifecycle Event](LifecycleEvent), wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.container_lifecycle("hello-world");
+    /// ```
+    pub fn container_lifecycle(
+        &self,
+        container_name: &str,
+    ) -> impl Stream<Item = Result<LifecycleEvent, Error>> {
+        let mut filters = HashMap::new();
+        filters.insert(String::from("type"), vec![String::from("container")]);
+        filters.insert(
+            String::from("container"),
+            vec![String::from(container_name)],
+        );
+
+        self.events(Some(EventsOptions {
+            filters,
+            ..Default::default()
+        }))
+        .try_filter_map(|event| future::ok(LifecycleEvent::from_event_message(&event)))
+    }
+
     /// ---
     ///
     /// # Get data usage information
@@ -304,8 +582,8 @@ impl Docker {
     /// # Returns
     ///
     ///  - [System Data Usage
-    ///  Response](SystemDataUsageResponse), wrapped in a
-    ///  Future.
+    ///    Response](SystemDataUsageResponse), wrapped in a
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -326,4 +604,407 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Data Usage Summary
+    ///
+    /// Fold the [data usage information](Docker::df()) into a single summary of the space used
+    /// and reclaimable on the host.
+    ///
+    /// # Returns
+    ///
+    ///  - [Disk Summary](DiskSummary), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.data_usage_summary();
+    /// ```
+    pub async fn data_usage_summary(&self) -> Result<DiskSummary, Error> {
+        self.df().await.map(|usage| DiskSummary::from(&usage))
+    }
+
+    /// ---
+    ///
+    /// # System Prune Dry Run
+    ///
+    /// List the dangling images, stopped containers, unused networks and unused volumes that a
+    /// prune would remove, without deleting anything.
+    ///
+    /// # Returns
+    ///
+    ///  - [Prune Candidates](PruneCandidates), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.system_prune_dry_run();
+    /// ```
+    pub async fn system_prune_dry_run(&self) -> Result<PruneCandidates, Error> {
+        let mut image_filters = HashMap::new();
+        image_filters.insert("dangling", vec!["true"]);
+
+        let mut container_filters = HashMap::new();
+        container_filters.insert("status", vec!["created", "exited", "dead"]);
+
+        let mut network_filters = HashMap::new();
+        network_filters.insert("dangling", vec!["true"]);
+
+        let mut volume_filters = HashMap::new();
+        volume_filters.insert("dangling", vec!["true"]);
+
+        let (images, containers, networks, volumes) = future::try_join4(
+            self.list_images(Some(ListImagesOptions {
+                filters: image_filters,
+                ..Default::default()
+            })),
+            self.list_containers(Some(ListContainersOptions {
+                all: true,
+                size: true,
+                filters: container_filters,
+                ..Default::default()
+            })),
+            self.list_networks(Some(ListNetworksOptions {
+                filters: network_filters,
+            })),
+            self.list_volumes(Some(ListVolumesOptions {
+                filters: volume_filters,
+            })),
+        )
+        .await?;
+
+        // Predefined networks are never pruned, even when no container uses them.
+        let networks: Vec<Network> = networks
+            .into_iter()
+            .filter(|network| {
+                !matches!(
+                    network.name.as_deref(),
+                    Some("bridge") | Some("host") | Some("none")
+                )
+            })
+            .collect();
+        let volumes = volumes.volumes.unwrap_or_default();
+
+        let reclaimable_bytes = images.iter().map(|image| image.size.max(0)).sum::<i64>()
+            + containers
+                .iter()
+                .filter_map(|container| container.size_rw)
+                .sum::<i64>()
+            + volumes
+                .iter()
+                .filter_map(|volume| volume.usage_data.as_ref())
+                .map(|usage| usage.size.max(0))
+                .sum::<i64>();
+
+        Ok(PruneCandidates {
+            images,
+            containers,
+            networks,
+            volumes,
+            reclaimable_bytes,
+        })
+    }
+
+    /// ---
+    ///
+    /// # Prune All With Report
+    ///
+    /// Prune stopped containers, unused networks, unused volumes, all unused images and the build
+    /// cache, in that order so that objects released by one category are pruned by the next. A
+    /// failure in one category is recorded in the report and does not stop the others.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Full Prune Report](FullPruneReport), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async move {
+    ///     let report = docker.prune_all_with_report().await;
+    ///     println!("reclaimed {} bytes", report.space_reclaimed);
+    /// };
+    /// ```
+    pub async fn prune_all_with_report(&self) -> FullPruneReport {
+        let containers = self
+            .prune_containers(None::<PruneContainersOptions<String>>)
+            .await;
+        let networks = self
+            .prune_networks(None::<PruneNetworksOptions<String>>)
+            .await;
+        let volumes = self
+            .prune_volumes(None::<PruneVolumesOptions<String>>)
+            .await;
+
+        let mut image_filters = HashMap::new();
+        image_filters.insert("dangling", vec!["false"]);
+        let images = self
+            .prune_images(Some(PruneImagesOptions {
+                filters: image_filters,
+            }))
+            .await;
+
+        let build_cache = self
+            .prune_build(Some(PruneBuildOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await;
+
+        let space_reclaimed = [
+            containers.as_ref().ok().and_then(|r| r.space_reclaimed),
+            volumes.as_ref().ok().and_then(|r| r.space_reclaimed),
+            images.as_ref().ok().and_then(|r| r.space_reclaimed),
+            build_cache.as_ref().ok().and_then(|r| r.space_reclaimed),
+        ]
+        .iter()
+        .flatten()
+        .sum();
+
+        FullPruneReport {
+            containers,
+            networks,
+            volumes,
+            images,
+            build_cache,
+            space_reclaimed,
+        }
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use futures_util::TryStreamExt;
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use crate::errors::Error;
+    use crate::{test_server, Docker, API_DEFAULT_VERSION};
+
+    use super::{DiskSummary, LifecycleEvent, PingResults};
+
+    #[tokio::test]
+    async fn test_ping_info() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nApi-Version:1.41\r\nBuilder-Version:2\r\nDocker-Experimental:false\r\nOstype:linux\r\nContent-Type:text/plain; charset=utf-8\r\n\r\nOK".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        assert_eq!(
+            docker.ping_info().await.unwrap(),
+            PingResults {
+                api_version: Some(String::from("1.41")),
+                os_type: Some(String::from("linux")),
+                experimental: false,
+                builder_version: Some(String::from("2")),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_all_with_report() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"SpaceReclaimed\":1024}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let report = docker.prune_all_with_report().await;
+
+        assert!(report.is_ok());
+        assert_eq!(report.space_reclaimed, 4 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_prune_all_with_report_partial_failure() {
+        let (docker, requests) = test_server::serve(|request| {
+            if request.contains("/volumes/prune") {
+                (
+                    "500 Internal Server Error",
+                    String::from("{\"message\":\"volume store is locked\"}"),
+                )
+            } else {
+                ("200 OK", String::from("{\"SpaceReclaimed\":1024}"))
+            }
+        })
+        .await;
+
+        let report = docker.prune_all_with_report().await;
+
+        assert!(!report.is_ok());
+        assert!(matches!(
+            report.volumes,
+            Err(Error::DockerResponseServerError { status_code: 500, ref message })
+                if message == "volume store is locked"
+        ));
+        assert!(report.containers.is_ok());
+        assert!(report.networks.is_ok());
+        assert!(report.images.is_ok());
+        assert!(report.build_cache.is_ok());
+        assert_eq!(report.space_reclaimed, 3 * 1024);
+        assert_eq!(requests.lock().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_container_lifecycle() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n\
+            {\"Type\":\"container\",\"Action\":\"start\",\"Actor\":{\"ID\":\"abc\"}}\n\
+            {\"Type\":\"container\",\"Action\":\"exec_start: ls\",\"Actor\":{\"ID\":\"abc\"}}\n\
+            {\"Type\":\"container\",\"Action\":\"health_status: unhealthy\",\"Actor\":{\"ID\":\"abc\"}}\n\
+            {\"Type\":\"container\",\"Action\":\"die\",\"Actor\":{\"ID\":\"abc\",\"Attributes\":{\"exitCode\":\"137\"}}}\n\
+            {\"Type\":\"container\",\"Action\":\"destroy\",\"Actor\":{\"ID\":\"abc\"}}\n"
+                .to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker
+            .container_lifecycle("abc")
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                LifecycleEvent::Started,
+                LifecycleEvent::Unhealthy,
+                LifecycleEvent::Died {
+                    exit_code: Some(137)
+                },
+                LifecycleEvent::Removed,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_data_usage_summary() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\
+            \"LayersSize\":1000,\
+            \"Images\":[\
+            {\"Id\":\"a\",\"ParentId\":\"\",\"RepoTags\":[],\"RepoDigests\":[],\"Created\":0,\"Size\":600,\"SharedSize\":400,\"VirtualSize\":600,\"Labels\":{},\"Containers\":1},\
+            {\"Id\":\"b\",\"ParentId\":\"\",\"RepoTags\":[],\"RepoDigests\":[],\"Created\":0,\"Size\":700,\"SharedSize\":400,\"VirtualSize\":700,\"Labels\":{},\"Containers\":0}],\
+            \"Containers\":[{\"SizeRw\":10,\"State\":\"running\"},{\"SizeRw\":20,\"State\":\"exited\"}],\
+            \"Volumes\":[\
+            {\"Name\":\"v1\",\"Driver\":\"local\",\"Mountpoint\":\"\",\"Labels\":{},\"Scope\":\"local\",\"Options\":{},\"UsageData\":{\"Size\":50,\"RefCount\":0}},\
+            {\"Name\":\"v2\",\"Driver\":\"local\",\"Mountpoint\":\"\",\"Labels\":{},\"Scope\":\"local\",\"Options\":{},\"UsageData\":{\"Size\":70,\"RefCount\":1}},\
+            {\"Name\":\"v3\",\"Driver\":\"nfs\",\"Mountpoint\":\"\",\"Labels\":{},\"Scope\":\"local\",\"Options\":{},\"UsageData\":{\"Size\":-1,\"RefCount\":-1}}],\
+            \"BuildCache\":[{\"Size\":5,\"InUse\":false,\"Shared\":false},{\"Size\":7,\"InUse\":true,\"Shared\":false},{\"Size\":9,\"InUse\":false,\"Shared\":true}]\
+            }"
+            .to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let result = docker.data_usage_summary().await.unwrap();
+
+        assert_eq!(
+            result,
+            DiskSummary {
+                images_bytes: 1000,
+                containers_bytes: 30,
+                volumes_bytes: 120,
+                build_cache_bytes: 12,
+                total_bytes: 1162,
+                reclaimable_bytes: 300 + 20 + 50 + 5,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_components() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\
+            \"Version\":\"20.10.17\",\
+            \"Components\":[\
+            {\"Name\":\"Engine\",\"Version\":\"20.10.17\",\"Details\":{\"ApiVersion\":\"1.41\",\"GitCommit\":\"a89b842\"}},\
+            {\"Name\":\"containerd\",\"Version\":\"1.6.6\",\"Details\":{\"GitCommit\":\"10c12954\"}},\
+            {\"Name\":\"runc\",\"Version\":\"1.1.2\",\"Details\":{\"GitCommit\":\"v1.1.2-0-ga916309\"}},\
+            {\"Name\":\"docker-init\",\"Version\":\"0.19.0\",\"Details\":{\"GitCommit\":\"de40ad0\"}}]}"
+                .to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let components = docker.version().await.unwrap().components.unwrap();
+
+        assert_eq!(
+            components
+                .iter()
+                .map(|component| (component.name.as_str(), component.version.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Engine", "20.10.17"),
+                ("containerd", "1.6.6"),
+                ("runc", "1.1.2"),
+                ("docker-init", "0.19.0"),
+            ]
+        );
+        assert_eq!(
+            components[1].details.as_ref().unwrap()["GitCommit"],
+            "10c12954"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_info_capabilities() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\
+            \"Plugins\":{\"Volume\":[\"local\"],\"Network\":[\"bridge\",\"overlay\"],\"Log\":[\"json-file\"]},\
+            \"Runtimes\":{\"runc\":{\"path\":\"runc\"},\"runsc\":{\"path\":\"/usr/local/bin/runsc\"}},\
+            \"DefaultRuntime\":\"runc\",\
+            \"SecurityOptions\":[\"name=seccomp,profile=default\"]}"
+                .to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let info = docker.info().await.unwrap();
+
+        let plugins = info.plugins.unwrap();
+        assert_eq!(plugins.volume, Some(vec![String::from("local")]));
+        assert_eq!(plugins.log, Some(vec![String::from("json-file")]));
+        assert_eq!(
+            info.runtimes.unwrap()["runsc"].path.as_deref(),
+            Some("/usr/local/bin/runsc")
+        );
+        assert_eq!(info.default_runtime.as_deref(), Some("runc"));
+        assert_eq!(
+            info.security_options,
+            Some(vec![String::from("name=seccomp,profile=default")])
+        );
+    }
 }
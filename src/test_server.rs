@@ -0,0 +1,96 @@
+//! A local HTTP server for tests that need to answer requests by path, or check the requests a
+//! method sends, which the host based mock connector cannot do.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use std::sync::{Arc, Mutex};
+
+use crate::{Docker, API_DEFAULT_VERSION};
+
+/// Start a server and connect a client to it. The method and target of every request, such as
+/// `GET /v1.40/info`, are recorded in order, and `reply` returns the status, such as `200 OK`,
+/// and the JSON body of the response to them.
+pub(crate) async fn serve<F>(reply: F) -> (Docker, Arc<Mutex<Vec<String>>>)
+where
+    F: Fn(&str) -> (&'static str, String) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let request = match read_request(&mut stream).await {
+                Some(request) => request,
+                None => continue,
+            };
+            let (status, body) = reply(&request);
+            recorded.lock().unwrap().push(request);
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type:application/json\r\nContent-Length:{}\r\nConnection:close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+
+    let docker =
+        Docker::connect_with_http(&format!("tcp://{}", addr), 5, API_DEFAULT_VERSION).unwrap();
+    (docker, requests)
+}
+
+/// Read a whole request, so the client is done sending its body before the response arrives, and
+/// return the method and target of its request line.
+async fn read_request(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0; 4096];
+
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return None,
+            Ok(len) => buf.extend_from_slice(&chunk[..len]),
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let header = |name: &str| {
+        head.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.eq_ignore_ascii_case(name) {
+                Some(value.trim().to_ascii_lowercase())
+            } else {
+                None
+            }
+        })
+    };
+    let content_length = header("content-length").and_then(|len| len.parse::<usize>().ok());
+    let chunked = header("transfer-encoding").as_deref() == Some("chunked");
+
+    loop {
+        let done = match content_length {
+            Some(len) => buf.len() >= header_end + len,
+            None => !chunked || buf.ends_with(b"0\r\n\r\n"),
+        };
+        if done {
+            break;
+        }
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return None,
+            Ok(len) => buf.extend_from_slice(&chunk[..len]),
+        }
+    }
+
+    let request_line = head.lines().next()?;
+    request_line
+        .rsplit_once(' ')
+        .map(|(request, _version)| request.to_string())
+}
@@ -26,21 +26,22 @@ impl<'a> Uri<'a> {
         client_type: &ClientType,
         path: &'a str,
         query: Option<O>,
-        client_version: &ClientVersion,
+        client_version: Option<&ClientVersion>,
     ) -> Result<Self, Error>
     where
         O: serde::ser::Serialize,
     {
+        let version_prefix = client_version
+            .map(|version| format!("/v{}.{}", version.major_version, version.minor_version))
+            .unwrap_or_default();
         let host_str = format!(
-            "{}://{}/v{}.{}{}",
+            "{}://{}{}{}",
             Uri::socket_scheme(client_type),
             Uri::socket_host(socket, client_type),
-            client_version.major_version,
-            client_version.minor_version,
+            version_prefix,
             path
         );
         let mut url = Url::parse(host_str.as_ref()).unwrap();
-        url = url.join(path).unwrap();
 
         if let Some(pairs) = query {
             let qs = serde_urlencoded::to_string(pairs)?;
@@ -86,8 +86,8 @@ where
 {
     /// Filters to process on the prune list, encoded as JSON.
     ///  - `label` (`label=<key>`, `label=<key>=<value>`, `label!=<key>`, or
-    ///  `label!=<key>=<value>`) Prune volumes with (or without, in case `label!=...` is used) the
-    ///  specified labels.
+    ///    `label!=<key>=<value>`) Prune volumes with (or without, in case `label!=...` is used) the
+    ///    specified labels.
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
 }
@@ -104,7 +104,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Volume List Response]VolumeListResponse) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -157,7 +157,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Volume](Volume) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -203,7 +203,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - A [Volume](Volume) struct, wrapped in a
-    ///  Future.
+    ///    Future.
     ///
     /// # Examples
     ///
@@ -325,3 +325,71 @@ impl Docker {
         self.process_into_value(req).await
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use std::collections::HashMap;
+
+    use super::{ListVolumesOptions, PruneVolumesOptions};
+    use crate::models::VolumeScopeEnum;
+
+    #[tokio::test]
+    async fn test_list_volumes() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"Volumes\":[{\"Name\":\"data\",\"Driver\":\"local\",\"Mountpoint\":\"/var/lib/docker/volumes/data/_data\",\"Labels\":{\"maintainer\":\"bollard\"},\"Scope\":\"local\",\"Options\":{}}],\"Warnings\":[\"volume driver unavailable\"]}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let mut filters = HashMap::new();
+        filters.insert("label", vec!["maintainer=bollard"]);
+
+        let result = docker
+            .list_volumes(Some(ListVolumesOptions { filters }))
+            .await
+            .unwrap();
+
+        let volumes = result.volumes.unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].name, "data");
+        assert_eq!(volumes[0].mountpoint, "/var/lib/docker/volumes/data/_data");
+        assert_eq!(volumes[0].labels["maintainer"], "bollard");
+        assert_eq!(volumes[0].scope, Some(VolumeScopeEnum::LOCAL));
+        assert_eq!(
+            result.warnings,
+            Some(vec![String::from("volume driver unavailable")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_volumes() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"VolumesDeleted\":[\"unused\"],\"SpaceReclaimed\":8192}".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let mut filters = HashMap::new();
+        filters.insert("label", vec!["maintainer=bollard"]);
+
+        let result = docker
+            .prune_volumes(Some(PruneVolumesOptions { filters }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.volumes_deleted, Some(vec![String::from("unused")]));
+        assert_eq!(result.space_reclaimed, Some(8192));
+    }
+}
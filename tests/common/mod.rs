@@ -138,27 +138,8 @@ pub async fn create_shell_daemon(
     docker: &Docker,
     container_name: &'static str,
 ) -> Result<(), Error> {
-    let image = if cfg!(windows) {
-        format!("{}nanoserver/iis", registry_http_addr())
-    } else {
-        format!("{}alpine", registry_http_addr())
-    };
-
-    let _ = &docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: &image[..],
-                ..Default::default()
-            }),
-            None,
-            if cfg!(windows) {
-                None
-            } else {
-                Some(integration_test_registry_credentials())
-            },
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
+    create_image_alpine(docker).await?;
+    let image = image_alpine();
 
     let result = &docker
         .create_container(
@@ -261,13 +242,52 @@ pub async fn kill_container(docker: &Docker, container_name: &'static str) -> Re
     Ok(())
 }
 
+/// The hello-world image of the test registry.
 #[allow(dead_code)]
-pub async fn create_image_hello_world(docker: &Docker) -> Result<(), Error> {
-    let image = if cfg!(windows) {
+pub fn image_hello_world() -> String {
+    if cfg!(windows) {
         format!("{}hello-world:nanoserver", registry_http_addr())
     } else {
         format!("{}hello-world:linux", registry_http_addr())
-    };
+    }
+}
+
+/// An image of the test registry with a shell, alpine on linux.
+#[allow(dead_code)]
+pub fn image_alpine() -> String {
+    if cfg!(windows) {
+        format!("{}nanoserver/iis", registry_http_addr())
+    } else {
+        format!("{}alpine", registry_http_addr())
+    }
+}
+
+#[allow(dead_code)]
+pub async fn create_image_alpine(docker: &Docker) -> Result<(), Error> {
+    let image = image_alpine();
+
+    let _ = &docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_image: &image[..],
+                ..Default::default()
+            }),
+            None,
+            if cfg!(windows) {
+                None
+            } else {
+                Some(integration_test_registry_credentials())
+            },
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub async fn create_image_hello_world(docker: &Docker) -> Result<(), Error> {
+    let image = image_hello_world();
 
     let result = &docker
         .create_image(
@@ -286,7 +306,7 @@ pub async fn create_image_hello_world(docker: &Docker) -> Result<(), Error> {
         .await?;
 
     assert_eq!(
-        result.get(0).unwrap().id.as_ref().unwrap(),
+        result.first().unwrap().id.as_ref().unwrap(),
         if cfg!(windows) { "nanoserver" } else { "linux" }
     );
 
@@ -4,8 +4,9 @@ use bollard::container::{
     AttachContainerOptions, AttachContainerResults, Config, CreateContainerOptions,
     DownloadFromContainerOptions, InspectContainerOptions, KillContainerOptions,
     ListContainersOptions, LogsOptions, PruneContainersOptions, RemoveContainerOptions,
-    RenameContainerOptions, ResizeContainerTtyOptions, RestartContainerOptions, StatsOptions,
-    TopOptions, UpdateContainerOptions, UploadToContainerOptions, WaitContainerOptions,
+    RenameContainerOptions, ResizeContainerTtyOptions, RestartContainerOptions,
+    StartContainerOptions, StatsOptions, TopOptions, UpdateContainerOptions,
+    UploadToContainerOptions, WaitContainerOptions,
 };
 use bollard::errors::Error;
 use bollard::image::{CreateImageOptions, PushImageOptions, TagImageOptions};
@@ -16,6 +17,7 @@ use futures_util::stream::TryStreamExt;
 use tokio::io::AsyncWriteExt;
 use tokio::runtime::Runtime;
 
+use std::collections::HashMap;
 use std::io::Write;
 
 #[macro_use]
@@ -166,6 +168,14 @@ async fn top_processes_test(docker: Docker) -> Result<(), Error> {
         .await?;
 
     assert_ne!(result.titles.as_ref().unwrap()[0].len(), 0);
+    if !cfg!(windows) {
+        assert!(result
+            .processes
+            .iter()
+            .flatten()
+            .flatten()
+            .any(|column| column.contains("uhttpd")));
+    }
     kill_container(&docker, "integration_test_top_processes").await?;
 
     Ok(())
@@ -254,7 +264,7 @@ async fn stats_test(docker: Docker) -> Result<(), Error> {
         .try_collect::<Vec<_>>()
         .await?;
 
-    let value = vec.get(0);
+    let value = vec.first();
 
     assert_eq!(value.unwrap().name, "/integration_test_stats".to_string());
     kill_container(&docker, "integration_test_stats")
@@ -272,6 +282,27 @@ async fn kill_container_test(docker: Docker) -> Result<(), Error> {
     let _ = &docker
         .kill_container("integration_test_kill_container", kill_options)
         .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_kill_container",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let result = &docker
+        .inspect_container(
+            "integration_test_kill_container",
+            None::<InspectContainerOptions>,
+        )
+        .await?;
+
+    assert_eq!(
+        result.state.as_ref().and_then(|state| state.running),
+        Some(false)
+    );
+
     let _ = &docker
         .remove_container(
             "integration_test_kill_container",
@@ -342,6 +373,236 @@ async fn attach_container_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn attach_container_stdin_test(docker: Docker) -> Result<(), Error> {
+    create_image_alpine(&docker).await?;
+    let image = image_alpine();
+
+    let _ = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_attach_container_stdin",
+            }),
+            Config {
+                image: Some(image),
+                cmd: if cfg!(windows) {
+                    Some(vec![
+                        String::from("cmd.exe"),
+                        String::from("/C"),
+                        String::from("more"),
+                    ])
+                } else {
+                    Some(vec![String::from("cat")])
+                },
+                open_stdin: Some(true),
+                stdin_once: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let AttachContainerResults { output, mut input } = docker
+        .attach_container(
+            "integration_test_attach_container_stdin",
+            Some(AttachContainerOptions::<String> {
+                stream: Some(true),
+                stdout: Some(true),
+                stdin: Some(true),
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    let _ = &docker
+        .start_container(
+            "integration_test_attach_container_stdin",
+            None::<StartContainerOptions<String>>,
+        )
+        .await?;
+
+    input.write_all(b"hello bollard\n").await?;
+    input.shutdown().await?;
+
+    let log: Vec<_> =
+        tokio::time::timeout(tokio::time::Duration::from_secs(10), output.try_collect())
+            .await
+            .expect("cat exits once stdin is closed")?;
+
+    let stdout = log
+        .iter()
+        .map(|output| output.to_string())
+        .collect::<String>();
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_attach_container_stdin",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_attach_container_stdin",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    assert_eq!(stdout.trim(), "hello bollard");
+
+    Ok(())
+}
+
+async fn wait_container_exit_code_test(docker: Docker) -> Result<(), Error> {
+    create_image_alpine(&docker).await?;
+    let image = image_alpine();
+
+    let _ = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_wait_container_exit_code",
+            }),
+            Config {
+                image: Some(image),
+                cmd: if cfg!(windows) {
+                    Some(vec!["cmd.exe", "/C", "exit 3"])
+                } else {
+                    Some(vec!["/bin/sh", "-c", "exit 3"])
+                }
+                .map(|cmd| cmd.into_iter().map(String::from).collect()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let wait = docker
+        .wait_container(
+            "integration_test_wait_container_exit_code",
+            Some(WaitContainerOptions {
+                condition: "next-exit",
+            }),
+        )
+        .try_collect::<Vec<_>>();
+
+    let start = async {
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        docker
+            .start_container(
+                "integration_test_wait_container_exit_code",
+                None::<StartContainerOptions<String>>,
+            )
+            .await
+    };
+
+    let (result, started) = futures_util::future::join(wait, start).await;
+    started?;
+
+    assert!(matches!(
+        result,
+        Err(Error::DockerContainerWaitError { code: 3, .. })
+    ));
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_wait_container_exit_code",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn list_containers_label_filter_test(docker: Docker) -> Result<(), Error> {
+    create_image_hello_world(&docker).await?;
+    let image = image_hello_world();
+
+    for (name, tier) in [
+        (
+            "integration_test_list_containers_label_frontend",
+            "frontend",
+        ),
+        ("integration_test_list_containers_label_backend", "backend"),
+    ] {
+        let mut labels = HashMap::new();
+        labels.insert("bollard.test.tier", tier);
+
+        let _ = &docker
+            .create_container(
+                Some(CreateContainerOptions { name }),
+                Config {
+                    image: Some(&image[..]),
+                    labels: Some(labels),
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+
+    let mut filters = HashMap::new();
+    filters.insert("label", vec!["bollard.test.tier=frontend"]);
+
+    let result = &docker
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters,
+            ..Default::default()
+        }))
+        .await?;
+
+    for name in [
+        "integration_test_list_containers_label_frontend",
+        "integration_test_list_containers_label_backend",
+    ] {
+        let _ = &docker
+            .remove_container(name, None::<RemoveContainerOptions>)
+            .await?;
+    }
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(
+        result[0].names,
+        Some(vec![String::from(
+            "/integration_test_list_containers_label_frontend"
+        )])
+    );
+
+    Ok(())
+}
+
+async fn remove_container_force_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_remove_container_force").await?;
+
+    let conflict = docker
+        .remove_container(
+            "integration_test_remove_container_force",
+            None::<RemoveContainerOptions>,
+        )
+        .await;
+
+    assert!(matches!(conflict, Err(ref e) if e.is_conflict()));
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_remove_container_force",
+            Some(RemoveContainerOptions {
+                force: true,
+                v: true,
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    let inspect = docker
+        .inspect_container(
+            "integration_test_remove_container_force",
+            None::<InspectContainerOptions>,
+        )
+        .await;
+
+    assert!(matches!(inspect, Err(ref e) if e.is_not_found()));
+
+    Ok(())
+}
+
 async fn resize_container_test(docker: Docker) -> Result<(), Error> {
     create_shell_daemon(&docker, "integration_test_resize_container_tty").await?;
 
@@ -479,6 +740,7 @@ async fn pause_container_test(docker: Docker) -> Result<(), Error> {
         ContainerStateStatusEnum::PAUSED,
         result.state.as_ref().unwrap().status.unwrap()
     );
+    assert_eq!(Some(true), result.state.as_ref().unwrap().paused);
 
     let _ = &docker
         .unpause_container("integration_test_pause_container")
@@ -495,6 +757,7 @@ async fn pause_container_test(docker: Docker) -> Result<(), Error> {
         ContainerStateStatusEnum::RUNNING,
         result.state.as_ref().unwrap().status.unwrap()
     );
+    assert_eq!(Some(false), result.state.as_ref().unwrap().paused);
 
     kill_container(&docker, "integration_test_pause_container").await?;
 
@@ -531,6 +794,66 @@ async fn prune_containers_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn prune_containers_label_filter_test(docker: Docker) -> Result<(), Error> {
+    create_image_hello_world(&docker).await?;
+    let image = image_hello_world();
+
+    let mut labels = HashMap::new();
+    labels.insert("bollard.test.prune", "true");
+
+    let container = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_prune_containers_label",
+            }),
+            Config {
+                image: Some(&image[..]),
+                labels: Some(labels),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let _ = &docker
+        .start_container(
+            "integration_test_prune_containers_label",
+            None::<StartContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_prune_containers_label",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut filters = HashMap::new();
+    filters.insert("label", vec!["bollard.test.prune=true"]);
+
+    let result = &docker
+        .prune_containers(Some(PruneContainersOptions { filters }))
+        .await?;
+
+    assert!(result
+        .containers_deleted
+        .as_ref()
+        .unwrap()
+        .contains(&container.id));
+
+    let err = docker
+        .inspect_container(
+            "integration_test_prune_containers_label",
+            None::<InspectContainerOptions>,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+
+    Ok(())
+}
+
 async fn archive_container_test(docker: Docker) -> Result<(), Error> {
     let image = if cfg!(windows) {
         format!("{}microsoft/nanoserver", registry_http_addr())
@@ -651,6 +974,63 @@ async fn archive_container_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(not(windows))]
+async fn download_hostname_from_container_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_download_hostname").await?;
+
+    let stat = docker
+        .stat_container_path(
+            "integration_test_download_hostname",
+            DownloadFromContainerOptions {
+                path: "/etc/hostname",
+            },
+        )
+        .await?;
+
+    assert_eq!("hostname", stat.name);
+
+    let res = docker.download_from_container(
+        "integration_test_download_hostname",
+        Some(DownloadFromContainerOptions {
+            path: "/etc/hostname",
+        }),
+    );
+
+    let bytes = concat_byte_stream(res).await?;
+
+    let mut a: tar::Archive<&[u8]> = tar::Archive::new(&bytes[..]);
+
+    let entries: Vec<_> = a
+        .entries()
+        .unwrap()
+        .map(|file| {
+            let file = file.unwrap();
+            (file.header().path().unwrap().into_owned(), file.size())
+        })
+        .collect();
+
+    assert_eq!(
+        vec![(std::path::PathBuf::from("hostname"), stat.size as u64)],
+        entries
+    );
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_download_hostname",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_download_hostname",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn inspect_container_test(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "integration_test_inspect_container").await?;
     let result = &docker
@@ -674,6 +1054,47 @@ async fn inspect_container_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn init_container_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    create_image_hello_world(&docker).await?;
+
+    let _ = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_init_container",
+            }),
+            Config {
+                image: Some(image),
+                host_config: Some(HostConfig {
+                    init: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let result = &docker
+        .inspect_container(
+            "integration_test_init_container",
+            None::<InspectContainerOptions>,
+        )
+        .await?;
+
+    assert_eq!(Some(true), result.host_config.as_ref().unwrap().init);
+
+    let _ = &docker
+        .remove_container("integration_test_init_container", None)
+        .await?;
+
+    Ok(())
+}
+
 async fn mount_volume_container_test(docker: Docker) -> Result<(), Error> {
     let image = if cfg!(windows) {
         format!("{}microsoft/nanoserver", registry_http_addr())
@@ -859,16 +1280,32 @@ fn integration_test_prune_containers() {
     connect_to_docker_and_run!(prune_containers_test);
 }
 
+#[test]
+fn integration_test_prune_containers_label_filter() {
+    connect_to_docker_and_run!(prune_containers_label_filter_test);
+}
+
 #[test]
 fn integration_test_archive_containers() {
     connect_to_docker_and_run!(archive_container_test);
 }
 
+#[test]
+#[cfg(not(windows))]
+fn integration_test_download_hostname_from_container() {
+    connect_to_docker_and_run!(download_hostname_from_container_test);
+}
+
 #[test]
 fn integration_test_inspect_containers() {
     connect_to_docker_and_run!(inspect_container_test);
 }
 
+#[test]
+fn integration_test_init_container() {
+    connect_to_docker_and_run!(init_container_test);
+}
+
 #[test]
 fn integration_test_mount_volume_containers() {
     connect_to_docker_and_run!(mount_volume_container_test);
@@ -879,6 +1316,26 @@ fn integration_test_attach_container() {
     connect_to_docker_and_run!(attach_container_test);
 }
 
+#[test]
+fn integration_test_attach_container_stdin() {
+    connect_to_docker_and_run!(attach_container_stdin_test);
+}
+
+#[test]
+fn integration_test_wait_container_exit_code() {
+    connect_to_docker_and_run!(wait_container_exit_code_test);
+}
+
+#[test]
+fn integration_test_list_containers_label_filter() {
+    connect_to_docker_and_run!(list_containers_label_filter_test);
+}
+
+#[test]
+fn integration_test_remove_container_force() {
+    connect_to_docker_and_run!(remove_container_force_test);
+}
+
 #[test]
 fn integration_test_resize_container_tty() {
     connect_to_docker_and_run!(resize_container_test);
@@ -10,6 +10,7 @@ use bollard::Docker;
 use futures_util::future;
 use futures_util::stream::TryStreamExt;
 use futures_util::StreamExt;
+use tokio::io::AsyncWriteExt;
 use tokio::runtime::Runtime;
 
 #[macro_use]
@@ -89,6 +90,243 @@ async fn start_exec_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn exec_simple_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_exec_simple_test").await?;
+
+    let output = &docker
+        .exec_simple(
+            "integration_test_exec_simple_test",
+            if cfg!(windows) {
+                vec!["cmd.exe", "/C", "echo out & echo err 1>&2 & exit 3"]
+            } else {
+                vec!["/bin/sh", "-c", "echo out; echo err >&2; exit 3"]
+            },
+        )
+        .await?;
+
+    assert_eq!(output.stdout.trim(), "out");
+    assert_eq!(output.stderr.trim(), "err");
+    assert_eq!(output.exit_code, 3);
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_exec_simple_test",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_exec_simple_test",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_exec_simple_test",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn start_exec_stdin_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_start_exec_stdin_test").await?;
+
+    let message = &docker
+        .create_exec(
+            "integration_test_start_exec_stdin_test",
+            CreateExecOptions {
+                attach_stdin: Some(true),
+                attach_stdout: Some(true),
+                cmd: if cfg!(windows) {
+                    Some(vec!["cmd.exe", "/C", "more"])
+                } else {
+                    Some(vec!["/bin/cat"])
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    if let StartExecResults::Attached { output, mut input } = docker
+        .start_exec(&message.id, None::<StartExecOptions>)
+        .await?
+    {
+        input.write_all(b"hello bollard\n").await?;
+        input.shutdown().await?;
+
+        let log: Vec<_> = output.try_collect().await?;
+        let stdout = log
+            .iter()
+            .map(|output| String::from_utf8_lossy(output.as_ref()).into_owned())
+            .collect::<String>();
+
+        assert_eq!(stdout.trim(), "hello bollard");
+    } else {
+        unreachable!();
+    }
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_start_exec_stdin_test",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_start_exec_stdin_test",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_start_exec_stdin_test",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn start_exec_echo_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_start_exec_echo_test").await?;
+
+    let message = &docker
+        .create_exec(
+            "integration_test_start_exec_echo_test",
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                cmd: if cfg!(windows) {
+                    Some(vec!["cmd.exe", "/C", "echo hello"])
+                } else {
+                    Some(vec!["/bin/echo", "hello"])
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    if let StartExecResults::Attached { output, .. } = docker
+        .start_exec(&message.id, None::<StartExecOptions>)
+        .await?
+    {
+        let log: Vec<_> = output.try_collect().await?;
+
+        assert!(matches!(
+            &log[..],
+            [LogOutput::StdOut { message }] if String::from_utf8_lossy(message).trim() == "hello"
+        ));
+    } else {
+        unreachable!();
+    }
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_start_exec_echo_test",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_start_exec_echo_test",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_start_exec_echo_test",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+async fn upload_to_container_exec_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_upload_to_container_exec_test").await?;
+
+    let mut header = tar::Header::new_gnu();
+    header.set_path("bollard.txt").unwrap();
+    header.set_size(19);
+    header.set_mode(0o644);
+    header.set_cksum();
+    let mut tar = tar::Builder::new(Vec::new());
+    tar.append(&header, &b"Hello from Bollard!"[..]).unwrap();
+    let payload = tar.into_inner().unwrap();
+
+    let _ = &docker
+        .upload_to_container(
+            "integration_test_upload_to_container_exec_test",
+            Some(UploadToContainerOptions {
+                path: "/tmp",
+                ..Default::default()
+            }),
+            payload.into(),
+        )
+        .await?;
+
+    let message = &docker
+        .create_exec(
+            "integration_test_upload_to_container_exec_test",
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                cmd: Some(vec!["/bin/cat", "/tmp/bollard.txt"]),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    if let StartExecResults::Attached { output, .. } = docker
+        .start_exec(&message.id, None::<StartExecOptions>)
+        .await?
+    {
+        let log: Vec<_> = output.try_collect().await?;
+        let content: Vec<u8> = log
+            .into_iter()
+            .flat_map(|line| line.into_bytes().to_vec())
+            .collect();
+
+        assert_eq!("Hello from Bollard!", String::from_utf8_lossy(&content));
+    } else {
+        unreachable!();
+    }
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_upload_to_container_exec_test",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_upload_to_container_exec_test",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_upload_to_container_exec_test",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn inspect_exec_test(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "integration_test_inspect_exec_test").await?;
 
@@ -160,6 +398,70 @@ async fn inspect_exec_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn inspect_exec_exit_code_test(docker: Docker) -> Result<(), Error> {
+    create_daemon(&docker, "integration_test_inspect_exec_exit_code_test").await?;
+
+    let message = &docker
+        .create_exec(
+            "integration_test_inspect_exec_exit_code_test",
+            CreateExecOptions {
+                cmd: if cfg!(windows) {
+                    Some(vec!["cmd.exe", "/C", "exit 7"])
+                } else {
+                    Some(vec!["/bin/sh", "-c", "exit 7"])
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    docker
+        .start_exec(
+            &message.id,
+            Some(StartExecOptions {
+                detach: true,
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    let mut exec_process = docker.inspect_exec(&message.id).await?;
+    for _ in 0..50 {
+        if exec_process.running != Some(true) {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        exec_process = docker.inspect_exec(&message.id).await?;
+    }
+
+    assert_eq!(exec_process.running, Some(false));
+    assert_eq!(exec_process.exit_code, Some(7));
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_inspect_exec_exit_code_test",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_inspect_exec_exit_code_test",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_inspect_exec_exit_code_test",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn start_exec_output_capacity_test_short(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "start_exec_output_capacity_test_short").await?;
 
@@ -321,11 +623,37 @@ fn integration_test_start_exec() {
     connect_to_docker_and_run!(start_exec_test);
 }
 
+#[test]
+fn integration_test_exec_simple() {
+    connect_to_docker_and_run!(exec_simple_test);
+}
+
+#[test]
+fn integration_test_start_exec_stdin() {
+    connect_to_docker_and_run!(start_exec_stdin_test);
+}
+
+#[test]
+fn integration_test_start_exec_echo() {
+    connect_to_docker_and_run!(start_exec_echo_test);
+}
+
+#[test]
+#[cfg(not(windows))]
+fn integration_test_upload_to_container_exec() {
+    connect_to_docker_and_run!(upload_to_container_exec_test);
+}
+
 #[test]
 fn integration_test_inspect_exec() {
     connect_to_docker_and_run!(inspect_exec_test);
 }
 
+#[test]
+fn integration_test_inspect_exec_exit_code() {
+    connect_to_docker_and_run!(inspect_exec_exit_code_test);
+}
+
 #[test]
 fn integration_test_start_exec_output_capacity_short() {
     connect_to_docker_and_run!(start_exec_output_capacity_test_short);
@@ -39,6 +39,24 @@ async fn search_images_test(docker: Docker) -> Result<(), Error> {
         .iter()
         .any(|api_image| &api_image.name.as_ref().unwrap()[..] == "hello-world"));
 
+    let mut filters = HashMap::new();
+    filters.insert("is-official", vec!["true"]);
+
+    let result = &docker
+        .search_images(SearchImagesOptions {
+            term: "alpine",
+            limit: Some(10),
+            filters,
+        })
+        .await?;
+
+    assert!(result.iter().any(|api_image| {
+        api_image.name.as_deref() == Some("alpine") && api_image.is_official == Some(true)
+    }));
+    assert!(result
+        .iter()
+        .all(|api_image| api_image.is_official == Some(true)));
+
     Ok::<_, Error>(())
 }
 
@@ -60,6 +78,19 @@ async fn inspect_image_test(docker: Docker) -> Result<(), Error> {
         .iter()
         .any(|repo_tag| repo_tag == &image));
 
+    let os = if cfg!(windows) { "windows" } else { "linux" };
+    assert_eq!(result.os.as_deref(), Some(os));
+    assert!(!result
+        .architecture
+        .as_deref()
+        .unwrap_or_default()
+        .is_empty());
+    assert!(result
+        .config
+        .as_ref()
+        .and_then(|config| config.cmd.as_ref())
+        .is_some());
+
     Ok(())
 }
 
@@ -118,6 +149,51 @@ async fn prune_images_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn tag_image_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    create_image_hello_world(&docker).await?;
+
+    let _ = &docker
+        .tag_image(
+            &image,
+            Some(TagImageOptions {
+                repo: "integration_test_tag_image",
+                tag: "bollard",
+            }),
+        )
+        .await?;
+
+    let mut filters = HashMap::new();
+    filters.insert("reference", vec!["integration_test_tag_image"]);
+
+    let result = &docker
+        .list_images(Some(ListImagesOptions {
+            filters,
+            ..Default::default()
+        }))
+        .await?;
+
+    assert!(result.iter().any(|api_image| api_image
+        .repo_tags
+        .iter()
+        .any(|repo_tag| repo_tag == "integration_test_tag_image:bollard")));
+
+    let _ = &docker
+        .remove_image(
+            "integration_test_tag_image:bollard",
+            None::<RemoveImageOptions>,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn remove_image_test(docker: Docker) -> Result<(), Error> {
     let image = if cfg!(windows) {
         format!("{}hello-world:nanoserver", registry_http_addr())
@@ -240,7 +316,7 @@ async fn commit_container_test(docker: Docker) -> Result<(), Error> {
         .try_collect::<Vec<_>>()
         .await?;
 
-    let first = vec.get(0).unwrap();
+    let first = vec.first().unwrap();
     if let Some(error) = &first.error {
         println!("{}", error.message.as_ref().unwrap());
     }
@@ -357,7 +433,7 @@ RUN touch bollard.txt
         .try_collect::<Vec<_>>()
         .await?;
 
-    let first = vec.get(0).unwrap();
+    let first = vec.first().unwrap();
     if let Some(error) = &first.error {
         println!("{}", error.message.as_ref().unwrap());
     }
@@ -416,6 +492,53 @@ async fn export_image_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn export_images_test(docker: Docker) -> Result<(), Error> {
+    create_image_hello_world(&docker).await?;
+
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    let archive = docker
+        .export_images(&[&image])
+        .try_collect::<Vec<_>>()
+        .await?
+        .concat();
+
+    let mut archive = tar::Archive::new(&archive[..]);
+    assert!(archive.entries()?.any(|entry| entry
+        .and_then(|entry| entry.path().map(|path| path.ends_with("manifest.json")))
+        .unwrap_or(false)));
+
+    Ok(())
+}
+
+async fn copy_image_to_host_tar_test(docker: Docker) -> Result<(), Error> {
+    create_image_hello_world(&docker).await?;
+
+    let image = format!("{}hello-world:linux", registry_http_addr());
+    let image_id = docker.inspect_image(&image).await?.id.unwrap();
+
+    let mut archive = Vec::new();
+    let written = docker
+        .copy_image_to_host_tar(&image, &mut archive, true, Some(&image_id))
+        .await?;
+    assert_eq!(written, archive.len() as u64);
+
+    let other_id = format!("sha256:{}", "0".repeat(64));
+    let result = docker
+        .copy_image_to_host_tar(&image, &mut Vec::new(), false, Some(&other_id))
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::ImageDigestMismatchError { expected, .. }) if expected == other_id
+    ));
+
+    Ok(())
+}
+
 async fn issue_55_test(docker: Docker) -> Result<(), Error> {
     let dockerfile = "FROM ubuntu:18.04
 RUN apt-get update && \
@@ -522,6 +645,20 @@ async fn import_image_test(docker: Docker) -> Result<(), Error> {
         .try_collect::<Vec<_>>()
         .await?;
 
+    let result = &docker
+        .list_images(Some(ListImagesOptions::<String> {
+            all: true,
+            ..Default::default()
+        }))
+        .await?;
+
+    assert!(result.iter().any(|api_image| {
+        api_image
+            .repo_tags
+            .iter()
+            .any(|repo_tag| repo_tag == &image)
+    }));
+
     Ok(())
 }
 // ND - Test sometimes hangs on appveyor.
@@ -536,6 +673,11 @@ fn integration_test_create_image() {
     connect_to_docker_and_run!(create_image_test);
 }
 
+#[test]
+fn integration_test_tag_image() {
+    connect_to_docker_and_run!(tag_image_test);
+}
+
 #[test]
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
@@ -583,6 +725,18 @@ fn integration_test_export_image() {
     connect_to_docker_and_run!(export_image_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_export_images() {
+    connect_to_docker_and_run!(export_images_test);
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_copy_image_to_host_tar() {
+    connect_to_docker_and_run!(copy_image_to_host_tar_test);
+}
+
 #[test]
 #[cfg(unix)]
 // Flaky
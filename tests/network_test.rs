@@ -102,7 +102,7 @@ async fn list_networks_test(docker: Docker) -> Result<(), Error> {
         }))
         .await?;
 
-    let v = results.get(0).unwrap();
+    let v = results.first().unwrap();
 
     assert!(v
         .ipam
@@ -175,6 +175,21 @@ async fn connect_network_test(docker: Docker) -> Result<(), Error> {
         .iter()
         .any(|(_, container)| container.ipv4_address == Some("10.10.10.101/24".into())));
 
+    let container_id = docker
+        .inspect_container(
+            "integration_test_connect_network_test",
+            None::<InspectContainerOptions>,
+        )
+        .await?
+        .id
+        .unwrap();
+
+    assert!(result
+        .containers
+        .as_ref()
+        .unwrap()
+        .contains_key(&container_id));
+
     let _ = &docker
         .disconnect_network(
             id,
@@ -185,6 +200,16 @@ async fn connect_network_test(docker: Docker) -> Result<(), Error> {
         )
         .await?;
 
+    let result = &docker
+        .inspect_network(id, None::<InspectNetworkOptions<&str>>)
+        .await?;
+
+    assert!(!result
+        .containers
+        .as_ref()
+        .map(|containers| containers.contains_key(&container_id))
+        .unwrap_or(false));
+
     let _ = &docker.remove_network(id).await?;
 
     let _ = &docker
@@ -1,4 +1,5 @@
 use bollard::auth::DockerCredentials;
+use bollard::container::RemoveContainerOptions;
 use bollard::errors::Error;
 use bollard::image::*;
 use bollard::models::*;
@@ -11,11 +12,14 @@ use futures_util::stream::StreamExt;
 use futures_util::stream::TryStreamExt;
 use tokio::runtime::Runtime;
 
+use std::collections::HashMap;
+
 #[macro_use]
 pub mod common;
 use common::*;
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum Results {
     CreateImageResults(CreateImageInfo),
     EventsResults(EventMessage),
@@ -61,10 +65,7 @@ async fn events_test(docker: Docker) -> Result<(), Error> {
 
     assert!(vec
         .iter()
-        .map(|value| {
-            println!("{:?}", value);
-            value
-        })
+        .inspect(|value| println!("{:?}", value))
         .any(|value| matches!(value, Results::EventsResults(EventMessage { typ: _, .. }))));
 
     Ok(())
@@ -125,6 +126,46 @@ async fn events_until_forever_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn events_container_start_test(docker: Docker) -> Result<(), Error> {
+    let mut filters = HashMap::new();
+    filters.insert("type", vec!["container"]);
+    filters.insert("event", vec!["start"]);
+    filters.insert("container", vec!["integration_test_events_container_start"]);
+
+    let mut events = docker.events(Some(EventsOptions {
+        filters,
+        ..Default::default()
+    }));
+
+    let start = async {
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        create_container_hello_world(&docker, "integration_test_events_container_start").await
+    };
+
+    let (event, _) = future::try_join(events.try_next(), start).await?;
+    let event = event.expect("start event");
+
+    assert_eq!(event.typ, Some(EventMessageTypeEnum::CONTAINER));
+    assert_eq!(event.action.as_deref(), Some("start"));
+    assert_eq!(
+        event
+            .actor
+            .and_then(|actor| actor.attributes)
+            .and_then(|attributes| attributes.get("name").cloned())
+            .as_deref(),
+        Some("integration_test_events_container_start")
+    );
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_events_container_start",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn df_test(docker: Docker) -> Result<(), Error> {
     create_image_hello_world(&docker).await?;
 
@@ -137,7 +178,31 @@ async fn df_test(docker: Docker) -> Result<(), Error> {
         .iter()
         .filter(|c: &&ImageSummary| c.repo_tags.iter().any(|r| r.contains("hello-world")));
 
-    assert!(c.count() > 0);
+    let c: Vec<_> = c.collect();
+    assert!(!c.is_empty());
+    assert!(c.iter().all(|image| image.size > 0));
+    assert!(result.layers_size.unwrap_or_default() > 0);
+
+    Ok(())
+}
+
+async fn system_prune_dry_run_test(docker: Docker) -> Result<(), Error> {
+    create_container_hello_world(&docker, "integration_test_system_prune_dry_run").await?;
+
+    let candidates = &docker.system_prune_dry_run().await?;
+
+    assert!(candidates.containers.iter().any(|container| container
+        .names
+        .iter()
+        .flatten()
+        .any(|name| name == "/integration_test_system_prune_dry_run")));
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_system_prune_dry_run",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
 
     Ok(())
 }
@@ -147,6 +212,20 @@ async fn info_test(docker: Docker) -> Result<(), Error> {
     let os_type = if cfg!(windows) { "windows" } else { "linux" };
 
     assert_eq!(os_type, res.os_type.as_ref().unwrap());
+    assert!(res.ncpu.unwrap_or_default() >= 1);
+    assert!(!res.server_version.as_deref().unwrap_or_default().is_empty());
+
+    Ok(())
+}
+
+async fn version_test(docker: Docker) -> Result<(), Error> {
+    let res = &docker.version().await?;
+
+    let api_version = res.api_version.as_deref().unwrap();
+    let (major, minor) = api_version.split_once('.').unwrap();
+    assert!(major.parse::<u32>().is_ok() && minor.parse::<u32>().is_ok());
+
+    assert!(!res.components.as_deref().unwrap_or_default().is_empty());
 
     Ok(())
 }
@@ -155,6 +234,9 @@ async fn ping_test(docker: Docker) -> Result<(), Error> {
     let res = &docker.ping().await?;
     assert_eq!("OK", res);
 
+    let res = &docker.ping_info().await?;
+    assert!(!res.api_version.as_deref().unwrap_or_default().is_empty());
+
     Ok(())
 }
 
@@ -163,6 +245,11 @@ fn integration_test_events() {
     connect_to_docker_and_run!(events_test);
 }
 
+#[test]
+fn integration_test_events_container_start() {
+    connect_to_docker_and_run!(events_container_start_test);
+}
+
 #[test]
 #[cfg(all(not(windows), any(feature = "chrono", feature = "time")))]
 fn integration_test_events_until_forever() {
@@ -175,11 +262,64 @@ fn integration_test_df() {
     connect_to_docker_and_run!(df_test);
 }
 
+#[test]
+fn integration_test_system_prune_dry_run() {
+    connect_to_docker_and_run!(system_prune_dry_run_test);
+}
+
 #[test]
 fn integration_test_info() {
     connect_to_docker_and_run!(info_test);
 }
 
+#[test]
+fn integration_test_version() {
+    connect_to_docker_and_run!(version_test);
+}
+
+#[test]
+#[cfg(windows)]
+fn integration_test_connect_with_named_pipe() {
+    let rt = Runtime::new().unwrap();
+    let docker = Docker::connect_with_named_pipe(
+        "npipe:////./pipe/docker_engine",
+        120,
+        bollard::API_DEFAULT_VERSION,
+    )
+    .unwrap();
+    run_runtime(rt, ping_test(docker));
+}
+
+#[test]
+#[cfg(feature = "test_ssl")]
+fn integration_test_connect_with_ssl() {
+    let rt = Runtime::new().unwrap();
+    let host = std::env::var("DOCKER_HOST").unwrap();
+    let cert_path = std::path::PathBuf::from(std::env::var("DOCKER_CERT_PATH").unwrap());
+    let docker = Docker::connect_with_ssl(
+        &host,
+        &cert_path.join("key.pem"),
+        &cert_path.join("cert.pem"),
+        &cert_path.join("ca.pem"),
+        120,
+        bollard::API_DEFAULT_VERSION,
+    )
+    .unwrap();
+    run_runtime(rt, ping_test(docker));
+}
+
+#[test]
+#[cfg(all(unix, not(feature = "test_http"), not(feature = "test_ssl")))]
+fn integration_test_connect_with_unix() {
+    let rt = Runtime::new().unwrap();
+    let path = std::env::var("DOCKER_HOST")
+        .ok()
+        .filter(|host| host.starts_with("unix://"))
+        .unwrap_or_else(|| String::from("unix:///var/run/docker.sock"));
+    let docker = Docker::connect_with_unix(&path, 120, bollard::API_DEFAULT_VERSION).unwrap();
+    run_runtime(rt, version_test(docker));
+}
+
 #[test]
 fn integration_test_ping() {
     connect_to_docker_and_run!(ping_test);
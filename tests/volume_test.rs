@@ -50,8 +50,12 @@ async fn list_volumes_test(docker: Docker) -> Result<(), Error> {
 }
 
 async fn create_volume_test(docker: Docker) -> Result<(), Error> {
+    let mut labels = HashMap::new();
+    labels.insert("maintainer", "bollard-maintainer");
+
     let create_volume_options = CreateVolumeOptions {
         name: "integration_test_create_volume",
+        labels,
         ..Default::default()
     };
 
@@ -59,6 +63,10 @@ async fn create_volume_test(docker: Docker) -> Result<(), Error> {
     let inspect_result = &docker.inspect_volume(&create_result.name).await?;
 
     assert_eq!(inspect_result.name, "integration_test_create_volume");
+    assert_eq!(
+        inspect_result.labels.get("maintainer").map(String::as_str),
+        Some("bollard-maintainer")
+    );
 
     let remove_volume_options = RemoveVolumeOptions { force: true };
     let _ = &docker
@@ -68,6 +76,12 @@ async fn create_volume_test(docker: Docker) -> Result<(), Error> {
         )
         .await?;
 
+    let inspect_result = docker
+        .inspect_volume("integration_test_create_volume")
+        .await;
+
+    assert!(matches!(inspect_result, Err(ref e) if e.is_not_found()));
+
     Ok(())
 }
 